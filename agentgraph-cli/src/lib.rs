@@ -0,0 +1,12 @@
+//! Library side of the `agentgraph` command-line runner: declarative graph
+//! specs, rendering and trace printing.
+
+mod nodes;
+mod render;
+mod show;
+mod spec;
+
+pub use nodes::{ChatNode, SetNode};
+pub use render::{render_dot, render_mermaid};
+pub use show::{format_record, show_file};
+pub use spec::{EdgeSpec, GraphSpec, NodeKind, NodeSpec};
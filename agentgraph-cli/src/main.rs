@@ -0,0 +1,122 @@
+use agentgraph_cli::{format_record, render_dot, render_mermaid, show_file, GraphSpec};
+use agentgraph_core::graph::GraphEvent;
+use agentgraph_core::types::JsonState;
+use agentgraph_core::Context;
+use anyhow::{bail, Context as _};
+use clap::{Parser, Subcommand, ValueEnum};
+use futures::StreamExt;
+use serde_json::Value;
+use std::io::Write;
+use std::path::PathBuf;
+
+/// Run declarative AgentGraph graphs and inspect their output
+#[derive(Parser)]
+#[command(name = "agentgraph", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a graph spec on an initial JSON state, printing node events as they stream
+    Run {
+        /// Graph spec file
+        spec: PathBuf,
+        /// Initial state file (defaults to `{}`)
+        #[arg(short, long)]
+        state: Option<PathBuf>,
+        /// Write every event to this file as JSON lines
+        #[arg(short, long)]
+        trace: Option<PathBuf>,
+        /// Only print the final state
+        #[arg(short, long)]
+        quiet: bool,
+    },
+    /// Render a graph spec as a diagram
+    Render {
+        /// Graph spec file
+        spec: PathBuf,
+        #[arg(short, long, value_enum, default_value_t = Format::Mermaid)]
+        format: Format,
+    },
+    /// Pretty-print a stored checkpoint or trace file
+    Show {
+        /// JSON or JSON lines file
+        file: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Mermaid,
+    Dot,
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    match Cli::parse().command {
+        Command::Run {
+            spec,
+            state,
+            trace,
+            quiet,
+        } => run(spec, state, trace, quiet).await,
+        Command::Render { spec, format } => {
+            let spec = GraphSpec::from_file(spec)?;
+            match format {
+                Format::Mermaid => print!("{}", render_mermaid(&spec)),
+                Format::Dot => print!("{}", render_dot(&spec)),
+            }
+            Ok(())
+        }
+        Command::Show { file } => {
+            println!("{}", show_file(file)?);
+            Ok(())
+        }
+    }
+}
+
+async fn run(
+    spec: PathBuf,
+    state: Option<PathBuf>,
+    trace: Option<PathBuf>,
+    quiet: bool,
+) -> anyhow::Result<()> {
+    let graph = GraphSpec::from_file(spec)?.build()?;
+    let initial_state = match state {
+        Some(path) => {
+            let text = std::fs::read_to_string(&path)
+                .with_context(|| format!("reading state {}", path.display()))?;
+            serde_json::from_str(&text)
+                .with_context(|| format!("parsing state {}", path.display()))?
+        }
+        None => Value::Object(Default::default()),
+    };
+    let mut trace = match trace {
+        Some(path) => Some(
+            std::fs::File::create(&path)
+                .with_context(|| format!("creating trace {}", path.display()))?,
+        ),
+        None => None,
+    };
+
+    let ctx = Context::default();
+    let events = graph.stream(&ctx, JsonState(initial_state));
+    futures::pin_mut!(events);
+    while let Some(event) = events.next().await {
+        let record = serde_json::to_value(&event)?;
+        if let Some(trace) = trace.as_mut() {
+            writeln!(trace, "{}", record)?;
+        }
+        match event {
+            GraphEvent::Node { .. } if quiet => {}
+            GraphEvent::End { state } if quiet => {
+                println!("{}", serde_json::to_string_pretty(&state)?)
+            }
+            GraphEvent::Error { message } => bail!(message),
+            _ => println!("{}", format_record(&record)),
+        }
+    }
+    Ok(())
+}
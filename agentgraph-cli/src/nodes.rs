@@ -0,0 +1,98 @@
+use agentgraph_core::completion::{ChatClient, ChatCompletionRequestOptions};
+use agentgraph_core::prebuilt::{self, MessagesState};
+use agentgraph_core::types::JsonState;
+use agentgraph_core::{Context, GraphState, Node, NodeError, NodeOutput, NodeResult};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+
+/// Merges a fixed JSON patch into the state
+#[derive(Debug)]
+pub struct SetNode {
+    name: String,
+    patch: Value,
+}
+
+impl SetNode {
+    pub fn new(name: impl Into<String>, patch: Value) -> Self {
+        Self {
+            name: name.into(),
+            patch,
+        }
+    }
+}
+
+#[async_trait]
+impl Node<JsonState> for SetNode {
+    async fn process(&self, _ctx: &Context, _state: JsonState) -> NodeResult<JsonState> {
+        Ok(NodeOutput::Updates(vec![self.patch.clone()]))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Runs the core `prebuilt::ChatNode` on the conversation stored in one field
+/// of the state and writes the history with the assistant's reply back
+pub struct ChatNode {
+    inner: prebuilt::ChatNode<MessagesState>,
+    messages_field: String,
+}
+
+impl ChatNode {
+    pub fn new(
+        name: impl Into<String>,
+        client: Arc<dyn ChatClient>,
+        options: ChatCompletionRequestOptions,
+        system: Option<String>,
+        messages_field: impl Into<String>,
+    ) -> Self {
+        let mut inner = prebuilt::ChatNode::new(client, options).with_name(name);
+        if let Some(system) = system {
+            inner = inner.with_system_prompt(system);
+        }
+        Self {
+            inner,
+            messages_field: messages_field.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Node<JsonState> for ChatNode {
+    async fn process(&self, ctx: &Context, state: JsonState) -> NodeResult<JsonState> {
+        let mut conversation = match state.0.get(&self.messages_field) {
+            Some(messages) => MessagesState::new(
+                serde_json::from_value(messages.clone())
+                    .map_err(|e| NodeError::Execution(e.to_string()))?,
+            ),
+            None => MessagesState::default(),
+        };
+
+        match self.inner.process(ctx, conversation.clone()).await? {
+            NodeOutput::Full(new_conversation) => conversation = new_conversation,
+            NodeOutput::Updates(updates) | NodeOutput::Command { updates, .. } => {
+                conversation.apply_many(updates)
+            }
+        }
+
+        // Merge patches replace arrays, so the whole history is sent back
+        let messages = serde_json::to_value(conversation.messages)
+            .map_err(|e| NodeError::Execution(e.to_string()))?;
+        let mut patch = serde_json::Map::new();
+        patch.insert(self.messages_field.clone(), messages);
+        Ok(NodeOutput::Updates(vec![Value::Object(patch)]))
+    }
+
+    fn name(&self) -> &str {
+        Node::<MessagesState>::name(&self.inner)
+    }
+}
+
+impl Debug for ChatNode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.debug_node(f)
+    }
+}
@@ -0,0 +1,142 @@
+use crate::spec::{EdgeSpec, GraphSpec, NodeKind};
+use std::collections::HashMap;
+
+const START_ID: &str = "__start__";
+const END_ID: &str = "__end__";
+
+/// Node id used in rendered output; `START` and `END` get ids that do not
+/// clash with reserved words
+fn node_id(name: &str) -> &str {
+    match name {
+        "START" => START_ID,
+        "END" => END_ID,
+        other => other,
+    }
+}
+
+/// Labelled transitions of the spec by node name: `(from, to, label)`
+fn transitions(spec: &GraphSpec) -> Vec<(&str, &str, Option<String>)> {
+    let mut transitions = vec![];
+    for edge in &spec.edges {
+        match edge {
+            EdgeSpec::Direct { from, to } => transitions.push((from.as_str(), to.as_str(), None)),
+            EdgeSpec::Conditional {
+                from,
+                pointer,
+                routes,
+                default,
+            } => {
+                for (value, to) in routes {
+                    let label = format!("{} = {}", pointer, value);
+                    transitions.push((from.as_str(), to.as_str(), Some(label)));
+                }
+                transitions.push((from.as_str(), default.as_str(), Some("default".into())));
+            }
+        }
+    }
+    transitions
+}
+
+/// Mermaid ids for every node name, including names only used by edges.
+/// Names may hold characters Mermaid reads as syntax, or be keywords such as
+/// `end`, so ids keep only ASCII letters, digits and underscores behind a
+/// `node_` prefix, with a numeric suffix where two names would collide.
+fn mermaid_ids(spec: &GraphSpec) -> HashMap<&str, String> {
+    let mut ids = HashMap::from([("START", START_ID.to_string()), ("END", END_ID.to_string())]);
+    let edge_names = transitions(spec)
+        .into_iter()
+        .flat_map(|(from, to, _)| [from, to]);
+    let names = spec.nodes.iter().map(|node| node.name.as_str());
+    for name in names.chain(edge_names) {
+        if ids.contains_key(name) {
+            continue;
+        }
+        let sanitized: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        let mut id = format!("node_{}", sanitized);
+        let mut suffix = 1;
+        while ids.values().any(|taken| *taken == id) {
+            suffix += 1;
+            id = format!("node_{}_{}", sanitized, suffix);
+        }
+        ids.insert(name, id);
+    }
+    ids
+}
+
+/// Text for a quoted Mermaid label, with quotes and markup escaped as
+/// entity codes
+fn mermaid_label(text: &str) -> String {
+    text.replace('#', "#35;")
+        .replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
+
+fn kind_name(kind: &NodeKind) -> &'static str {
+    match kind {
+        NodeKind::Set { .. } => "set",
+        NodeKind::Chat { .. } => "chat",
+    }
+}
+
+/// Render the spec as a Mermaid flowchart
+pub fn render_mermaid(spec: &GraphSpec) -> String {
+    let ids = mermaid_ids(spec);
+    let id = |name: &str| ids[name].as_str();
+
+    let mut out = String::from("flowchart TD\n");
+    out.push_str(&format!("    {}([\"START\"])\n", START_ID));
+    out.push_str(&format!("    {}([\"END\"])\n", END_ID));
+    for node in &spec.nodes {
+        out.push_str(&format!(
+            "    {}[\"{}<br/><i>{}</i>\"]\n",
+            id(&node.name),
+            mermaid_label(&node.name),
+            kind_name(&node.kind)
+        ));
+    }
+    for (from, to, label) in transitions(spec) {
+        match label {
+            Some(label) => out.push_str(&format!(
+                "    {} -.->|\"{}\"| {}\n",
+                id(from),
+                mermaid_label(&label),
+                id(to)
+            )),
+            None => out.push_str(&format!("    {} --> {}\n", id(from), id(to))),
+        }
+    }
+    out
+}
+
+/// Render the spec as a Graphviz DOT digraph
+pub fn render_dot(spec: &GraphSpec) -> String {
+    let mut out = format!("digraph {:?} {{\n", spec.name);
+    out.push_str(&format!(
+        "    {:?} [label=\"START\", shape=oval];\n",
+        START_ID
+    ));
+    out.push_str(&format!("    {:?} [label=\"END\", shape=oval];\n", END_ID));
+    for node in &spec.nodes {
+        out.push_str(&format!(
+            "    {:?} [label={:?}, shape=box];\n",
+            node.name,
+            format!("{}\n({})", node.name, kind_name(&node.kind))
+        ));
+    }
+    for (from, to, label) in transitions(spec) {
+        let (from, to) = (node_id(from), node_id(to));
+        match label {
+            Some(label) => out.push_str(&format!(
+                "    {:?} -> {:?} [label={:?}, style=dashed];\n",
+                from, to, label
+            )),
+            None => out.push_str(&format!("    {:?} -> {:?};\n", from, to)),
+        }
+    }
+    out.push_str("}\n");
+    out
+}
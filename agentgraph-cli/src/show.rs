@@ -0,0 +1,46 @@
+use anyhow::Context as _;
+use serde_json::Value;
+use std::path::Path;
+
+/// Format one stored record for display. Graph events written by
+/// `agentgraph run --trace` get a header line; anything else is printed as
+/// pretty JSON.
+pub fn format_record(record: &Value) -> String {
+    let pretty = |value: &Value| serde_json::to_string_pretty(value).unwrap_or_default();
+    match record.get("type").and_then(Value::as_str) {
+        Some("node") => format!(
+            "── node: {} ──\n{}",
+            record["node"].as_str().unwrap_or("?"),
+            pretty(&record["state"])
+        ),
+        Some("end") => format!("── end ──\n{}", pretty(&record["state"])),
+        Some("error") => format!(
+            "── error ──\n{}",
+            record["message"].as_str().unwrap_or_default()
+        ),
+        _ => pretty(record),
+    }
+}
+
+/// Pretty-print a stored checkpoint or trace file, either a single JSON
+/// document or JSON lines
+pub fn show_file(path: impl AsRef<Path>) -> anyhow::Result<String> {
+    let path = path.as_ref();
+    let text =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+
+    if let Ok(document) = serde_json::from_str::<Value>(&text) {
+        return Ok(format_record(&document));
+    }
+
+    let mut records = vec![];
+    for (number, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: Value = serde_json::from_str(line)
+            .with_context(|| format!("parsing {} line {}", path.display(), number + 1))?;
+        records.push(format_record(&record));
+    }
+    Ok(records.join("\n"))
+}
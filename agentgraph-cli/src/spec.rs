@@ -0,0 +1,196 @@
+use crate::nodes::{ChatNode, SetNode};
+use agentgraph_core::completion::{ChatClient, ChatClientImpl, ChatCompletionRequestOptions};
+use agentgraph_core::node::NodeConfig;
+use agentgraph_core::types::JsonState;
+use agentgraph_core::{Built, Graph, END, START};
+use anyhow::{anyhow, Context as _};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A graph over `JsonState` described in JSON.
+///
+/// Edges may use `START` and `END` for the graph's entry and exit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GraphSpec {
+    pub name: String,
+    pub nodes: Vec<NodeSpec>,
+    #[serde(default)]
+    pub edges: Vec<EdgeSpec>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeSpec {
+    pub name: String,
+    #[serde(flatten)]
+    pub kind: NodeKind,
+    /// Maximum retries for node execution
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_retries: Option<usize>,
+    /// Timeout for node execution in seconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<u64>,
+}
+
+/// What a declared node does
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NodeKind {
+    /// Merge a fixed JSON patch into the state
+    Set { patch: Value },
+    /// Send the messages in `messages_field` to a chat model and append its reply
+    Chat {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        temperature: Option<f32>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        system: Option<String>,
+        #[serde(default = "default_messages_field")]
+        messages_field: String,
+    },
+}
+
+fn default_messages_field() -> String {
+    "messages".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EdgeSpec {
+    /// Route on the value found at a JSON pointer into the state. Strings are
+    /// matched as-is, other values by their JSON text (e.g. `true`).
+    Conditional {
+        from: String,
+        pointer: String,
+        routes: BTreeMap<String, String>,
+        default: String,
+    },
+    Direct {
+        from: String,
+        to: String,
+    },
+}
+
+/// Map the `START` and `END` aliases used in specs to graph node names
+pub(crate) fn resolve(name: &str) -> &str {
+    match name {
+        "START" => START,
+        "END" => END,
+        other => other,
+    }
+}
+
+impl GraphSpec {
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)
+            .with_context(|| format!("reading graph spec {}", path.display()))?;
+        serde_json::from_str(&text)
+            .with_context(|| format!("parsing graph spec {}", path.display()))
+    }
+
+    /// Whether any node needs a chat model
+    pub fn uses_chat(&self) -> bool {
+        self.nodes
+            .iter()
+            .any(|node| matches!(node.kind, NodeKind::Chat { .. }))
+    }
+
+    /// Build the graph, creating an OpenAI client from `OPENAI_API_KEY` if
+    /// any node needs one
+    pub fn build(&self) -> anyhow::Result<Graph<JsonState, Built>> {
+        let client = if self.uses_chat() {
+            let api_key = std::env::var("OPENAI_API_KEY")
+                .map_err(|_| anyhow!("OPENAI_API_KEY must be set for chat nodes"))?;
+            Some(Arc::new(ChatClientImpl::new(api_key)) as Arc<dyn ChatClient>)
+        } else {
+            None
+        };
+        self.build_with_client(client)
+    }
+
+    /// Build the graph with the given chat client for chat nodes
+    pub fn build_with_client(
+        &self,
+        client: Option<Arc<dyn ChatClient>>,
+    ) -> anyhow::Result<Graph<JsonState, Built>> {
+        let mut graph = Graph::new(self.name.clone());
+
+        for node in &self.nodes {
+            match &node.kind {
+                NodeKind::Set { patch } => {
+                    graph.add_node(SetNode::new(node.name.clone(), patch.clone()));
+                }
+                NodeKind::Chat {
+                    model,
+                    temperature,
+                    system,
+                    messages_field,
+                } => {
+                    let client = client
+                        .clone()
+                        .ok_or_else(|| anyhow!("Node {} needs a chat client", node.name))?;
+                    let mut options = ChatCompletionRequestOptions::default();
+                    if let Some(model) = model {
+                        options.model = model.clone();
+                    }
+                    if temperature.is_some() {
+                        options.temperature = *temperature;
+                    }
+                    graph.add_node(ChatNode::new(
+                        node.name.clone(),
+                        client,
+                        options,
+                        system.clone(),
+                        messages_field.clone(),
+                    ));
+                }
+            }
+
+            if node.max_retries.is_some() || node.timeout.is_some() {
+                let default = NodeConfig::default();
+                graph.configure_node(
+                    node.name.clone(),
+                    NodeConfig {
+                        max_retries: node.max_retries.unwrap_or(default.max_retries),
+                        timeout: node.timeout.unwrap_or(default.timeout),
+                    },
+                );
+            }
+        }
+
+        for edge in &self.edges {
+            match edge {
+                EdgeSpec::Direct { from, to } => {
+                    graph.add_edge(resolve(from), resolve(to));
+                }
+                EdgeSpec::Conditional {
+                    from,
+                    pointer,
+                    routes,
+                    default,
+                } => {
+                    let pointer = pointer.clone();
+                    let routes: BTreeMap<String, String> = routes
+                        .iter()
+                        .map(|(value, to)| (value.clone(), resolve(to).to_string()))
+                        .collect();
+                    let default = resolve(default).to_string();
+                    graph.add_conditional_edge(resolve(from), move |state: &JsonState| {
+                        let key = match state.pointer(&pointer) {
+                            Some(Value::String(s)) => s.clone(),
+                            Some(value) => value.to_string(),
+                            None => "null".to_string(),
+                        };
+                        routes.get(&key).unwrap_or(&default).clone()
+                    });
+                }
+            }
+        }
+
+        Ok(graph.try_build()?)
+    }
+}
@@ -0,0 +1,197 @@
+use agentgraph_cli::{format_record, render_dot, render_mermaid, GraphSpec};
+use agentgraph_core::completion::{
+    ChatClient, ChatClientImpl, ChatCompletionCallOptions, ChatCompletionRequestOptions,
+};
+use agentgraph_core::types::JsonState;
+use agentgraph_core::Context;
+use async_openai::types::{
+    ChatCompletionRequestMessage, CreateChatCompletionRequest, CreateChatCompletionResponse,
+    CreateChatCompletionStreamResponse,
+};
+use async_trait::async_trait;
+use futures::Stream;
+use serde_json::json;
+use std::pin::Pin;
+use std::sync::Arc;
+
+fn demo_spec() -> GraphSpec {
+    serde_json::from_value(json!({
+        "name": "demo",
+        "nodes": [
+            { "name": "greet", "type": "set", "patch": { "greeting": "hello" } },
+            { "name": "finish", "type": "set", "patch": { "done": true }, "max_retries": 1 }
+        ],
+        "edges": [
+            { "from": "START", "to": "greet" },
+            { "from": "greet", "pointer": "/done", "routes": { "true": "END" }, "default": "finish" },
+            { "from": "finish", "to": "greet" }
+        ]
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_run_spec() {
+    let graph = demo_spec().build().unwrap();
+    let ctx = Context::new("test");
+    let result = graph
+        .run(&ctx, JsonState(json!({ "user": "ryan" })))
+        .await
+        .unwrap();
+    assert_eq!(
+        result.0,
+        json!({ "user": "ryan", "greeting": "hello", "done": true })
+    );
+    assert_eq!(graph.node_config("finish").unwrap().max_retries, 1);
+}
+
+#[test]
+fn test_invalid_spec() {
+    let spec: GraphSpec = serde_json::from_value(json!({
+        "name": "broken",
+        "nodes": [{ "name": "a", "type": "set", "patch": {} }],
+        "edges": [{ "from": "START", "to": "a" }, { "from": "a", "to": "missing" }]
+    }))
+    .unwrap();
+    assert!(spec.build().is_err());
+}
+
+#[test]
+fn test_render() {
+    let spec = demo_spec();
+
+    let mermaid = render_mermaid(&spec);
+    assert!(mermaid.starts_with("flowchart TD\n"));
+    assert!(mermaid.contains("__start__ --> node_greet"));
+    assert!(mermaid.contains("node_greet -.->|\"/done = true\"| __end__"));
+
+    let dot = render_dot(&spec);
+    assert!(dot.starts_with("digraph \"demo\" {"));
+    assert!(dot.contains("\"finish\" -> \"greet\";"));
+    assert!(dot.contains("\"greet\" -> \"finish\" [label=\"default\", style=dashed];"));
+}
+
+#[test]
+fn test_render_mermaid_names() {
+    let spec: GraphSpec = serde_json::from_value(json!({
+        "name": "names",
+        "nodes": [
+            { "name": "end", "type": "set", "patch": {} },
+            { "name": "say \"hi\"", "type": "set", "patch": {} },
+            { "name": "say__hi_", "type": "set", "patch": {} }
+        ],
+        "edges": [
+            { "from": "START", "to": "end" },
+            { "from": "end", "to": "say \"hi\"" },
+            { "from": "say \"hi\"", "to": "say__hi_" },
+            { "from": "say__hi_", "to": "END" }
+        ]
+    }))
+    .unwrap();
+
+    let mermaid = render_mermaid(&spec);
+    assert!(mermaid.contains("    node_end[\"end<br/><i>set</i>\"]\n"));
+    assert!(mermaid.contains("    node_say__hi_[\"say #quot;hi#quot;<br/><i>set</i>\"]\n"));
+    assert!(mermaid.contains("    node_say__hi__2[\"say__hi_<br/><i>set</i>\"]\n"));
+    assert!(mermaid.contains("node_end --> node_say__hi_\n"));
+    assert!(mermaid.contains("node_say__hi_ --> node_say__hi__2\n"));
+}
+
+#[test]
+fn test_format_record() {
+    let record = json!({ "type": "node", "node": "greet", "state": { "a": 1 } });
+    assert_eq!(
+        format_record(&record),
+        "── node: greet ──\n{\n  \"a\": 1\n}"
+    );
+    let record = json!({ "type": "error", "message": "boom" });
+    assert_eq!(format_record(&record), "── error ──\nboom");
+}
+
+// Chat client that always answers with the same text
+struct EchoClient {
+    inner: ChatClientImpl,
+}
+
+#[async_trait]
+impl ChatClient for EchoClient {
+    fn create_chat_completion_request(
+        &self,
+        messages: Vec<ChatCompletionRequestMessage>,
+        options: &ChatCompletionRequestOptions,
+    ) -> Result<CreateChatCompletionRequest, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner.create_chat_completion_request(messages, options)
+    }
+
+    fn create_chat_completion_stream_request(
+        &self,
+        messages: Vec<ChatCompletionRequestMessage>,
+        options: &ChatCompletionRequestOptions,
+    ) -> Result<CreateChatCompletionRequest, Box<dyn std::error::Error + Send + Sync>> {
+        self.inner
+            .create_chat_completion_stream_request(messages, options)
+    }
+
+    async fn complete(
+        &self,
+        request: CreateChatCompletionRequest,
+        _options: Option<ChatCompletionCallOptions>,
+    ) -> Result<CreateChatCompletionResponse, Box<dyn std::error::Error + Send + Sync>> {
+        // The system prompt is sent but not stored in the state
+        assert_eq!(request.messages.len(), 2);
+        Ok(serde_json::from_value(json!({
+            "id": "test",
+            "object": "chat.completion",
+            "created": 0,
+            "model": request.model,
+            "choices": [{
+                "index": 0,
+                "message": { "role": "assistant", "content": "hi there" },
+                "finish_reason": "stop"
+            }]
+        }))?)
+    }
+
+    async fn complete_stream(
+        &self,
+        _request: CreateChatCompletionRequest,
+        _options: Option<ChatCompletionCallOptions>,
+    ) -> Result<
+        Pin<
+            Box<
+                dyn Stream<
+                        Item = Result<
+                            CreateChatCompletionStreamResponse,
+                            Box<dyn std::error::Error + Send + Sync>,
+                        >,
+                    > + Send,
+            >,
+        >,
+        Box<dyn std::error::Error + Send + Sync>,
+    > {
+        Err("streaming is not supported".into())
+    }
+}
+
+#[tokio::test]
+async fn test_chat_node() {
+    let spec: GraphSpec = serde_json::from_value(json!({
+        "name": "chat",
+        "nodes": [{ "name": "model", "type": "chat", "system": "Be brief" }],
+        "edges": [{ "from": "START", "to": "model" }, { "from": "model", "to": "END" }]
+    }))
+    .unwrap();
+    let client = EchoClient {
+        inner: ChatClientImpl::new("test".to_string()),
+    };
+    let graph = spec.build_with_client(Some(Arc::new(client))).unwrap();
+
+    let ctx = Context::new("test");
+    let state = JsonState(json!({ "messages": [{ "role": "user", "content": "hello" }] }));
+    let result = graph.run(&ctx, state).await.unwrap();
+
+    let messages = result.pointer("/messages").unwrap().as_array().unwrap();
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[1]["role"], "assistant");
+    assert_eq!(messages[1]["content"], "hi there");
+}
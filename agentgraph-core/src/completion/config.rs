@@ -0,0 +1,37 @@
+use super::ChatCompletionRequestOptions;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Common run-time knobs for nodes that call a chat model. Read it with
+/// `ctx.config::<ModelConfig>()` and apply it over the node's defaults.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct ModelConfig {
+    /// Model to use instead of the node's default
+    pub model: Option<String>,
+    /// Sampling temperature to use instead of the node's default
+    pub temperature: Option<f32>,
+    /// System prompt to use instead of the node's default. It is part of the
+    /// messages rather than the request options, see `system_prompt_or`.
+    pub system_prompt: Option<String>,
+}
+
+impl ModelConfig {
+    /// Copy of `options` with the configured model and temperature applied.
+    /// The system prompt is not a request option and is left to the caller.
+    pub fn apply(&self, options: &ChatCompletionRequestOptions) -> ChatCompletionRequestOptions {
+        let mut options = options.clone();
+        if let Some(model) = &self.model {
+            options.model = model.clone();
+        }
+        if self.temperature.is_some() {
+            options.temperature = self.temperature;
+        }
+        options
+    }
+
+    /// The configured system prompt, or `default` if none is set
+    pub fn system_prompt_or<'a>(&'a self, default: Option<&'a str>) -> Option<&'a str> {
+        self.system_prompt.as_deref().or(default)
+    }
+}
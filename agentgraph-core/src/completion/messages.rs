@@ -0,0 +1,90 @@
+use async_openai::types::ChatCompletionRequestMessage;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A chat message tagged with an id, so later updates can replace or remove it.
+/// Use with `#[update(messages)]` on a `Vec<Message>` field.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct Message {
+    /// Identifier of the message within the history
+    pub id: String,
+    /// The message, or `None` for a marker removing the message with this id
+    #[schemars(with = "Option<serde_json::Value>")]
+    pub message: Option<ChatCompletionRequestMessage>,
+}
+
+impl Message {
+    /// Wrap a message under a fresh random id
+    pub fn new(message: impl Into<ChatCompletionRequestMessage>) -> Self {
+        Self::with_id(uuid::Uuid::new_v4().to_string(), message)
+    }
+
+    /// Wrap a message under the given id, replacing any message with that id
+    pub fn with_id(
+        id: impl Into<String>,
+        message: impl Into<ChatCompletionRequestMessage>,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            message: Some(message.into()),
+        }
+    }
+
+    /// Marker removing the message with the given id
+    pub fn remove(id: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            message: None,
+        }
+    }
+
+    /// Whether this is a removal marker
+    pub fn is_removal(&self) -> bool {
+        self.message.is_none()
+    }
+}
+
+/// Merge `updates` into a message history: a message whose id is already
+/// present replaces it in place, a removal marker deletes it, and any other
+/// message is appended. Removal markers for unknown ids are ignored.
+pub fn add_messages(history: &mut Vec<Message>, updates: Vec<Message>) {
+    for update in updates {
+        let existing = history.iter().position(|m| m.id == update.id);
+        match (existing, update.message.is_some()) {
+            (Some(index), true) => history[index] = update,
+            (Some(index), false) => {
+                history.remove(index);
+            }
+            (None, true) => history.push(update),
+            (None, false) => {}
+        }
+    }
+}
+
+/// The chat messages of a history, in order, ready to send to a `ChatClient`
+pub fn chat_messages(history: &[Message]) -> Vec<ChatCompletionRequestMessage> {
+    history.iter().filter_map(|m| m.message.clone()).collect()
+}
+
+/// Updates that turn history `old` into `new` under `add_messages`, or `None`
+/// if `new` reorders messages kept from `old`
+pub fn diff_messages(old: &[Message], new: &[Message]) -> Option<Vec<Message>> {
+    let in_new = |message: &&Message| new.iter().any(|m| m.id == message.id);
+    let kept: Vec<&Message> = old.iter().filter(in_new).collect();
+    // Kept messages stay in place, so they must lead `new` in the same order
+    if kept.iter().zip(new).any(|(kept, new)| kept.id != new.id) {
+        return None;
+    }
+
+    let mut updates: Vec<Message> = old
+        .iter()
+        .filter(|message| !in_new(message))
+        .map(|message| Message::remove(message.id.clone()))
+        .collect();
+    for (index, message) in new.iter().enumerate() {
+        if kept.get(index) != Some(&message) {
+            updates.push(message.clone());
+        }
+    }
+    Some(updates)
+}
@@ -1,7 +1,11 @@
 mod client;
+mod config;
+mod messages;
 mod tracing;
 
 pub use client::{
     ChatClient, ChatClientImpl, ChatCompletionCallOptions, ChatCompletionRequestOptions,
 };
+pub use config::ModelConfig;
+pub use messages::{add_messages, chat_messages, diff_messages, Message};
 pub use tracing::{LangSmithTracer, TracingError, TracingProvider};
@@ -1,11 +1,14 @@
 use async_trait::async_trait;
+use futures::StreamExt;
 use std::collections::HashMap;
 use std::fmt::Debug;
 use std::sync::Arc;
 
 use super::*;
 use crate::node::*;
+use crate::tool::JsonSchema;
 use crate::types::*;
+use crate::update_log::{Change, RecordWrite, UpdateRecord, UpdateSink};
 
 pub const START: &str = "_START_";
 pub const END: &str = "_END_";
@@ -13,10 +16,13 @@ pub const END: &str = "_END_";
 /// A graph that executes nodes in a defined order
 #[derive(Debug)]
 pub struct Graph<State, BuildState = NotBuilt> {
-    graph_name: String,
-    nodes: HashMap<String, Arc<dyn Node<State>>>,
-    edges: HashMap<String, Edge<State>>,
-    configs: HashMap<String, NodeConfig>,
+    pub(crate) graph_name: String,
+    pub(crate) nodes: HashMap<String, Arc<dyn Node<State>>>,
+    pub(crate) edges: HashMap<String, Edge<State>>,
+    pub(crate) configs: HashMap<String, NodeConfig>,
+    pub(crate) destinations: HashMap<String, Vec<String>>,
+    pub(crate) config_schema: Option<serde_json::Value>,
+    pub(crate) update_sink: Option<Arc<dyn UpdateSink<State>>>,
     _build_state: std::marker::PhantomData<BuildState>,
 }
 
@@ -31,6 +37,9 @@ where
             nodes: HashMap::new(),
             edges: HashMap::new(),
             configs: HashMap::new(),
+            destinations: HashMap::new(),
+            config_schema: None,
+            update_sink: None,
             _build_state: std::marker::PhantomData,
         }
     }
@@ -60,21 +69,98 @@ where
         self
     }
 
+    /// Declare the targets a node may jump to by returning `NodeOutput::Command`
+    pub fn add_destinations<I, T>(&mut self, from: impl Into<String>, targets: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<String>,
+    {
+        self.destinations
+            .entry(from.into())
+            .or_default()
+            .extend(targets.into_iter().map(Into::into));
+        self
+    }
+
+    /// Declare the run configuration type nodes read with `Context::config`,
+    /// so callers can discover its schema
+    pub fn set_config_schema<C: JsonSchema>(&mut self) -> &mut Self {
+        self.config_schema = Some(C::schema());
+        self
+    }
+
+    /// Record every state change to `sink` before it is applied
+    pub fn set_update_sink(&mut self, sink: Arc<dyn UpdateSink<S>>) -> &mut Self {
+        self.update_sink = Some(sink);
+        self
+    }
+
     /// Configure a node with specific settings
     pub fn configure_node(&mut self, name: impl Into<String>, config: NodeConfig) -> &mut Self {
         self.configs.insert(name.into(), config);
         self
     }
 
+    /// Check that every edge and declared destination refers to a known node,
+    /// and that every node has somewhere to go once it finishes
+    pub fn validate(&self) -> GraphResult<()> {
+        let is_target = |name: &str| name == END || self.nodes.contains_key(name);
+
+        for (from, edge) in &self.edges {
+            if from != START && !self.nodes.contains_key(from) {
+                return Err(GraphError::NodeNotFound(from.clone()));
+            }
+            if let Edge::Direct(to) = edge {
+                if !is_target(to) {
+                    return Err(GraphError::InvalidTransition(format!(
+                        "Edge from {} points to unknown node: {}",
+                        from, to
+                    )));
+                }
+            }
+        }
+
+        for (from, targets) in &self.destinations {
+            if !self.nodes.contains_key(from) {
+                return Err(GraphError::NodeNotFound(from.clone()));
+            }
+            if let Some(to) = targets.iter().find(|to| !is_target(to)) {
+                return Err(GraphError::InvalidTransition(format!(
+                    "Node {} declares unknown destination: {}",
+                    from, to
+                )));
+            }
+        }
+
+        for name in self.nodes.keys() {
+            if !self.edges.contains_key(name) && !self.destinations.contains_key(name) {
+                return Err(GraphError::InvalidTransition(format!(
+                    "No transition defined from node: {}",
+                    name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate the graph and build it, making it ready for execution
+    pub fn try_build(self) -> GraphResult<Graph<S, Built>> {
+        self.validate()?;
+        Ok(self.build())
+    }
+
     /// Build the graph, making it ready for execution
     pub fn build(self) -> Graph<S, Built> {
-        // Validate graph structure here
-        // For now, we just transform the state
+        // Validation is opt-in through `validate` or `try_build`
         Graph {
             graph_name: self.graph_name,
             nodes: self.nodes,
             edges: self.edges,
             configs: self.configs,
+            destinations: self.destinations,
+            config_schema: self.config_schema,
+            update_sink: self.update_sink,
             _build_state: std::marker::PhantomData,
         }
     }
@@ -86,32 +172,40 @@ where
 {
     /// Run the graph with an initial state
     pub async fn run(&self, ctx: &Context, initial_state: S) -> GraphResult<S> {
+        self.run_observed(ctx, initial_state, &mut |_, _| {}).await
+    }
+
+    /// Run the graph, calling `observer` with the node name and the resulting
+    /// state every time a node completes
+    pub(crate) async fn run_observed(
+        &self,
+        ctx: &Context,
+        initial_state: S,
+        observer: &mut (dyn FnMut(&str, &S) + Send),
+    ) -> GraphResult<S> {
+        // Start the log with the initial state so the run can be replayed
+        let run_id = uuid::Uuid::new_v4().to_string();
+        if let Some(sink) = &self.update_sink {
+            let write = sink.record(&UpdateRecord {
+                timestamp: chrono::Utc::now(),
+                run_id: run_id.clone(),
+                trace_id: ctx.trace_id.clone(),
+                node: START.to_string(),
+                attempt: 0,
+                change: Change::Start(&initial_state),
+            });
+            write.await?;
+        }
+
         let mut current_state = initial_state;
         let mut current_node = START.to_string();
+        let mut goto: Option<String> = None;
 
         while current_node != END {
-            // Get next node based on edges
-            let next_node = match self.edges.get(&current_node) {
-                Some(Edge::Direct(next)) => next.clone(),
-                Some(Edge::Conditional(condition)) => {
-                    let current_state_ref = &current_state;
-                    condition(current_state_ref)
-                }
-                None => {
-                    if current_node == START {
-                        // If we're at START with no edge, try to find a default starting node
-                        self.nodes
-                            .keys()
-                            .next()
-                            .ok_or_else(|| GraphError::InvalidState("Graph has no nodes".into()))?
-                            .clone()
-                    } else {
-                        return Err(GraphError::InvalidTransition(format!(
-                            "No transition defined from node: {}",
-                            current_node
-                        )));
-                    }
-                }
+            // Get next node from the previous node's command, or based on edges
+            let next_node = match goto.take() {
+                Some(next) => next,
+                None => self.route(&current_node, &current_state)?,
             };
 
             // Check if we've reached the end
@@ -170,15 +264,25 @@ where
                 }
             }?;
 
-            // Now apply each update to our state
-            current_state = match updates {
-                NodeOutput::Full(new_state) => new_state,
-                NodeOutput::Updates(updates) => {
-                    let mut new_state = current_state.clone();
-                    new_state.apply_many(updates);
-                    new_state
-                }
-            };
+            // Encode the log records and apply the changes to a copy of the state
+            let (new_state, target, writes) = self.settle(
+                &current_state,
+                updates,
+                &run_id,
+                &node_ctx,
+                &next_node,
+                attempts,
+            )?;
+            if target.is_some() {
+                goto = target;
+            }
+
+            // The new state only takes effect once its changes are logged
+            for write in writes {
+                write.await?;
+            }
+            current_state = new_state;
+            observer(&next_node, &current_state);
 
             // Move on
             current_node = next_node;
@@ -186,6 +290,121 @@ where
 
         Ok(current_state)
     }
+
+    /// Run the graph once per initial state, with at most `concurrency` runs in
+    /// flight. Each run gets its own child context of `ctx`, and results are
+    /// returned in input order, so one failed run does not fail the batch.
+    pub async fn run_batch(
+        &self,
+        ctx: &Context,
+        initial_states: Vec<S>,
+        concurrency: usize,
+    ) -> Vec<GraphResult<S>> {
+        futures::stream::iter(initial_states)
+            .map(|state| {
+                let run_ctx = ctx.next_node_context();
+                async move { self.run(&run_ctx, state).await }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await
+    }
+
+    /// Check a node's output, encode its update log records if the graph has
+    /// an update log, and apply it to a copy of `state`. Returns the new state,
+    /// the `Command` destination if any, and the pending log writes.
+    fn settle(
+        &self,
+        state: &S,
+        updates: NodeOutput<S>,
+        run_id: &str,
+        ctx: &Context,
+        node: &str,
+        attempt: usize,
+    ) -> GraphResult<(S, Option<String>, Vec<RecordWrite>)> {
+        // Reject the jump before anything is logged or applied
+        if let NodeOutput::Command { goto: target, .. } = &updates {
+            self.check_goto(node, target)?;
+        }
+
+        let mut writes = vec![];
+        if let Some(sink) = &self.update_sink {
+            let changes: Vec<Change<&S::Update, &S>> = match &updates {
+                NodeOutput::Full(new_state) => vec![Change::Full(new_state)],
+                NodeOutput::Updates(updates) | NodeOutput::Command { updates, .. } => {
+                    updates.iter().map(Change::Update).collect()
+                }
+            };
+            for change in changes {
+                writes.push(sink.record(&UpdateRecord {
+                    timestamp: chrono::Utc::now(),
+                    run_id: run_id.to_string(),
+                    trace_id: ctx.trace_id.clone(),
+                    node: node.to_string(),
+                    attempt,
+                    change,
+                }));
+            }
+        }
+
+        Ok(match updates {
+            NodeOutput::Full(new_state) => (new_state, None, writes),
+            NodeOutput::Updates(updates) => {
+                let mut new_state = state.clone();
+                new_state.apply_many(updates);
+                (new_state, None, writes)
+            }
+            NodeOutput::Command { updates, goto } => {
+                let mut new_state = state.clone();
+                new_state.apply_many(updates);
+                (new_state, Some(goto), writes)
+            }
+        })
+    }
+
+    /// Check that a `NodeOutput::Command` from `from` may jump to `target`:
+    /// it must be a registered node or `END`, and one of the node's declared
+    /// destinations if it has any
+    fn check_goto(&self, from: &str, target: &str) -> GraphResult<()> {
+        if target != END && !self.nodes.contains_key(target) {
+            return Err(GraphError::InvalidTransition(format!(
+                "Node {} cannot jump to unknown node: {}",
+                from, target
+            )));
+        }
+        if let Some(allowed) = self.destinations.get(from) {
+            if !allowed.iter().any(|to| to == target) {
+                return Err(GraphError::InvalidTransition(format!(
+                    "Node {} cannot jump to undeclared destination: {}",
+                    from, target
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Resolve the next node from the outgoing edge of `current_node`
+    fn route(&self, current_node: &str, current_state: &S) -> GraphResult<String> {
+        match self.edges.get(current_node) {
+            Some(Edge::Direct(next)) => Ok(next.clone()),
+            Some(Edge::Conditional(condition)) => Ok(condition(current_state)),
+            None => {
+                if current_node == START {
+                    // If we're at START with no edge, try to find a default starting node
+                    self.nodes
+                        .keys()
+                        .next()
+                        .cloned()
+                        .ok_or_else(|| GraphError::InvalidState("Graph has no nodes".into()))
+                } else {
+                    Err(GraphError::InvalidTransition(format!(
+                        "No transition defined from node: {}",
+                        current_node
+                    )))
+                }
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -204,4 +423,8 @@ where
     fn name(&self) -> &str {
         &self.graph_name
     }
+
+    fn subgraph(&self) -> Option<&Graph<S, Built>> {
+        Some(self)
+    }
 }
@@ -0,0 +1,136 @@
+use std::fmt::{Debug, Formatter, Result};
+
+use super::*;
+use crate::node::NodeConfig;
+use crate::types::GraphState;
+
+/// A node reached while walking a graph and its subgraphs
+pub struct NodeVisit<'a, S> {
+    /// Names of the enclosing graphs, outermost first
+    pub path: Vec<&'a str>,
+    /// Name of the node within its enclosing graph
+    pub name: &'a str,
+    /// Outgoing edge of the node, if any
+    pub edge: Option<&'a Edge<S>>,
+    /// Targets the node may jump to with `NodeOutput::Command`
+    pub destinations: &'a [String],
+    /// Effective configuration of the node
+    pub config: NodeConfig,
+    /// Whether the node is itself a graph
+    pub is_subgraph: bool,
+}
+
+impl<S> Debug for NodeVisit<'_, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("NodeVisit")
+            .field("path", &self.path)
+            .field("name", &self.name)
+            .field("edge", &self.edge)
+            .field("destinations", &self.destinations)
+            .field("config", &self.config)
+            .field("is_subgraph", &self.is_subgraph)
+            .finish()
+    }
+}
+
+impl<S> Graph<S, Built>
+where
+    S: GraphState,
+{
+    /// Name of the graph
+    pub fn graph_name(&self) -> &str {
+        &self.graph_name
+    }
+
+    /// JSON Schema of the run configuration declared with `set_config_schema`
+    pub fn config_schema(&self) -> Option<&serde_json::Value> {
+        self.config_schema.as_ref()
+    }
+
+    /// Names of all nodes in the graph, sorted
+    pub fn node_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.nodes.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Whether the graph contains a node with the given name
+    pub fn has_node(&self, name: &str) -> bool {
+        self.nodes.contains_key(name)
+    }
+
+    /// Outgoing edge of a node, or of `START`
+    pub fn edge(&self, from: &str) -> Option<&Edge<S>> {
+        self.edges.get(from)
+    }
+
+    /// Targets a node has declared it may jump to with `NodeOutput::Command`
+    pub fn destinations(&self, from: &str) -> &[String] {
+        self.destinations.get(from).map_or(&[], Vec::as_slice)
+    }
+
+    /// Statically known successors of a node, from its direct edge and its
+    /// declared destinations. Targets of conditional edges are only known at
+    /// run time and are not included.
+    pub fn successors(&self, from: &str) -> Vec<&str> {
+        let mut successors = vec![];
+        if let Some(Edge::Direct(to)) = self.edges.get(from) {
+            successors.push(to.as_str());
+        }
+        for to in self.destinations(from) {
+            if !successors.contains(&to.as_str()) {
+                successors.push(to.as_str());
+            }
+        }
+        successors
+    }
+
+    /// Nodes (or `START`) that statically lead to the given node, sorted.
+    /// Conditional edges are not included.
+    pub fn predecessors(&self, to: &str) -> Vec<&str> {
+        let mut predecessors: Vec<&str> = self
+            .edges
+            .keys()
+            .chain(self.destinations.keys())
+            .map(String::as_str)
+            .filter(|from| self.successors(from).contains(&to))
+            .collect();
+        predecessors.sort_unstable();
+        predecessors.dedup();
+        predecessors
+    }
+
+    /// Configuration used when running a node, falling back to the default
+    pub fn node_config(&self, name: &str) -> Option<NodeConfig> {
+        self.nodes
+            .contains_key(name)
+            .then(|| self.configs.get(name).cloned().unwrap_or_default())
+    }
+
+    /// Walk every node of the graph in name order, descending into subgraph
+    /// nodes right after visiting them
+    pub fn walk(&self) -> Vec<NodeVisit<'_, S>> {
+        let mut visits = vec![];
+        self.walk_into(&mut vec![], &mut visits);
+        visits
+    }
+
+    fn walk_into<'a>(&'a self, path: &mut Vec<&'a str>, visits: &mut Vec<NodeVisit<'a, S>>) {
+        path.push(&self.graph_name);
+        for name in self.node_names() {
+            let subgraph = self.nodes[name].subgraph();
+            visits.push(NodeVisit {
+                path: path.clone(),
+                name,
+                edge: self.edge(name),
+                destinations: self.destinations(name),
+                config: self.configs.get(name).cloned().unwrap_or_default(),
+                is_subgraph: subgraph.is_some(),
+            });
+            if let Some(subgraph) = subgraph {
+                subgraph.walk_into(path, visits);
+            }
+        }
+        path.pop();
+    }
+}
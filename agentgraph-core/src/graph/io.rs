@@ -0,0 +1,100 @@
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+
+use super::*;
+use crate::node::Context;
+use crate::tool::JsonSchema;
+use crate::types::{GraphResult, GraphState};
+
+/// A built graph behind a public input and output type. Callers pass an `I`,
+/// which is converted into the graph's state, and get back an `O` projected
+/// from the final state, so internal fields never reach them.
+pub struct IoGraph<S, I, O> {
+    graph: Graph<S, Built>,
+    _io: PhantomData<fn(I) -> O>,
+}
+
+impl<S> Graph<S, Built>
+where
+    S: GraphState,
+{
+    /// Put a public input and output type in front of the graph's state
+    pub fn with_io<I, O>(self) -> IoGraph<S, I, O>
+    where
+        I: Into<S>,
+        O: From<S>,
+    {
+        IoGraph {
+            graph: self,
+            _io: PhantomData,
+        }
+    }
+}
+
+impl<S, I, O> IoGraph<S, I, O>
+where
+    S: GraphState,
+    I: Into<S>,
+    O: From<S>,
+{
+    /// Run the graph on the state built from `input` and project the result
+    pub async fn invoke(&self, ctx: &Context, input: I) -> GraphResult<O> {
+        let state = self.graph.run(ctx, input.into()).await?;
+        Ok(O::from(state))
+    }
+
+    /// Run the graph once per input, as in `Graph::run_batch`
+    pub async fn invoke_batch(
+        &self,
+        ctx: &Context,
+        inputs: Vec<I>,
+        concurrency: usize,
+    ) -> Vec<GraphResult<O>> {
+        let states = inputs.into_iter().map(Into::into).collect();
+        self.graph
+            .run_batch(ctx, states, concurrency)
+            .await
+            .into_iter()
+            .map(|result| result.map(O::from))
+            .collect()
+    }
+
+    /// The wrapped graph, e.g. for introspection
+    pub fn graph(&self) -> &Graph<S, Built> {
+        &self.graph
+    }
+
+    /// Unwrap the graph, dropping the input and output types
+    pub fn into_graph(self) -> Graph<S, Built> {
+        self.graph
+    }
+
+    /// JSON Schema of the input type
+    pub fn input_schema() -> serde_json::Value
+    where
+        I: JsonSchema,
+    {
+        I::schema()
+    }
+
+    /// JSON Schema of the output type
+    pub fn output_schema() -> serde_json::Value
+    where
+        O: JsonSchema,
+    {
+        O::schema()
+    }
+}
+
+impl<S, I, O> Debug for IoGraph<S, I, O>
+where
+    S: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("IoGraph")
+            .field("graph", &self.graph)
+            .field("input", &std::any::type_name::<I>())
+            .field("output", &std::any::type_name::<O>())
+            .finish()
+    }
+}
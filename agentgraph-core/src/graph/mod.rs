@@ -1,9 +1,17 @@
 mod builder;
 mod core;
 mod edges;
+mod introspect;
+mod io;
 mod marker;
+#[cfg(feature = "streaming")]
+mod stream;
 mod tests;
 
 pub use core::{Graph, END, START};
 pub use edges::{Condition, Edge};
+pub use introspect::NodeVisit;
+pub use io::IoGraph;
 pub use marker::{Built, NotBuilt};
+#[cfg(feature = "streaming")]
+pub use stream::GraphEvent;
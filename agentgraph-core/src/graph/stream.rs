@@ -0,0 +1,61 @@
+use futures::Stream;
+use serde::Serialize;
+
+use super::*;
+use crate::node::Context;
+use crate::types::GraphState;
+
+/// Event emitted while streaming a graph run
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GraphEvent<S> {
+    /// A node completed and produced a new state
+    Node { node: String, state: S },
+    /// The run reached `END` with this final state
+    End { state: S },
+    /// The run failed
+    Error { message: String },
+}
+
+impl<S> Graph<S, Built>
+where
+    S: GraphState,
+{
+    /// Run the graph, yielding an event after every node and a final `End` or
+    /// `Error` event
+    pub fn stream<'a>(
+        &'a self,
+        ctx: &'a Context,
+        initial_state: S,
+    ) -> impl Stream<Item = GraphEvent<S>> + Send + 'a {
+        async_stream::stream! {
+            let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+            let mut observer = move |node: &str, state: &S| {
+                let _ = tx.send(GraphEvent::Node {
+                    node: node.to_string(),
+                    state: state.clone(),
+                });
+            };
+            let run = self.run_observed(ctx, initial_state, &mut observer);
+            tokio::pin!(run);
+
+            let result = loop {
+                tokio::select! {
+                    biased;
+                    Some(event) = rx.recv() => yield event,
+                    result = &mut run => break result,
+                }
+            };
+            while let Ok(event) = rx.try_recv() {
+                yield event;
+            }
+
+            yield match result {
+                Ok(state) => GraphEvent::End { state },
+                Err(e) => GraphEvent::Error {
+                    message: e.to_string(),
+                },
+            };
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use agentgraph_core::graph::IoGraph;
     use agentgraph_core::*;
     use agentgraph_macros::State;
     use std::sync::Arc;
@@ -95,6 +96,322 @@ mod tests {
         assert_eq!(result.count, 6);
     }
 
+    #[tokio::test]
+    async fn test_command_routing() {
+        let router = FunctionNode::new("router", |_ctx, state: CounterState| async move {
+            let goto = if state.count < 5 { "double" } else { END };
+            Ok(NodeOutput::goto(
+                vec![CounterStateUpdate::Count(state.count + 1)],
+                goto,
+            ))
+        });
+
+        let double = FunctionNode::new("double", |_ctx, state: CounterState| async move {
+            Ok(NodeOutput::Updates(vec![CounterStateUpdate::Count(
+                state.count * 2,
+            )]))
+        });
+
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(router)
+                .add_node(double)
+                .add_edge(START, "router")
+                .add_destinations("router", ["double", END])
+                .add_edge("double", END);
+            graph.try_build().unwrap()
+        };
+
+        let ctx = Context::new("test");
+        let result = built_graph
+            .run(&ctx, CounterState { count: 1 })
+            .await
+            .unwrap();
+        // 1 + 1 = 2, 2 * 2 = 4
+        assert_eq!(result.count, 4);
+
+        let result = built_graph
+            .run(&ctx, CounterState { count: 5 })
+            .await
+            .unwrap();
+        assert_eq!(result.count, 6);
+    }
+
+    #[tokio::test]
+    async fn test_command_undeclared_destination() {
+        let router = FunctionNode::new("router", |_ctx, _state: CounterState| async move {
+            Ok(NodeOutput::goto(vec![], "elsewhere"))
+        });
+
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(router)
+                .add_edge(START, "router")
+                .add_destinations("router", [END]);
+            graph.build()
+        };
+
+        let ctx = Context::new("test");
+        let result = built_graph.run(&ctx, CounterState { count: 0 }).await;
+        assert!(matches!(result, Err(GraphError::InvalidTransition(_))));
+    }
+
+    #[tokio::test]
+    async fn test_command_unknown_node() {
+        let router = FunctionNode::new("router", |_ctx, state: CounterState| async move {
+            let goto = if state.count == 0 { "missing" } else { END };
+            Ok(NodeOutput::goto(vec![CounterStateUpdate::Count(1)], goto))
+        });
+
+        // Without declared destinations any node may be targeted, but it
+        // still has to exist
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(router)
+                .add_edge(START, "router")
+                .add_edge("router", END);
+            graph.try_build().unwrap()
+        };
+
+        let ctx = Context::new("test");
+        let result = built_graph.run(&ctx, CounterState { count: 0 }).await;
+        match result {
+            Err(GraphError::InvalidTransition(message)) => {
+                assert_eq!(message, "Node router cannot jump to unknown node: missing")
+            }
+            other => panic!("expected InvalidTransition, got {:?}", other),
+        }
+        let result = built_graph.run(&ctx, CounterState { count: 1 }).await;
+        assert_eq!(result.unwrap().count, 1);
+    }
+
+    #[test]
+    fn test_validate() {
+        let node = || {
+            FunctionNode::new("node1", |_ctx, _state: CounterState| async move {
+                Ok(NodeOutput::Updates(vec![]))
+            })
+        };
+
+        // No way out of node1
+        let mut graph = Graph::new("g");
+        graph.add_node(node()).add_edge(START, "node1");
+        assert!(matches!(
+            graph.validate(),
+            Err(GraphError::InvalidTransition(_))
+        ));
+
+        // Destination that does not exist
+        let mut graph = Graph::new("g");
+        graph
+            .add_node(node())
+            .add_edge(START, "node1")
+            .add_destinations("node1", ["missing"]);
+        assert!(graph.try_build().is_err());
+
+        let mut graph = Graph::new("g");
+        graph
+            .add_node(node())
+            .add_edge(START, "node1")
+            .add_destinations("node1", [END]);
+        assert!(graph.try_build().is_ok());
+    }
+
+    #[test]
+    fn test_introspection() {
+        let node = |name: &'static str| {
+            FunctionNode::new(name, |_ctx, _state: CounterState| async move {
+                Ok(NodeOutput::Updates(vec![]))
+            })
+        };
+
+        let inner = {
+            let mut graph = Graph::new("inner");
+            graph
+                .add_node(node("leaf"))
+                .add_edge(START, "leaf")
+                .add_edge("leaf", END);
+            graph.build()
+        };
+
+        let outer = {
+            let mut graph = Graph::new("outer");
+            graph
+                .add_node(node("first"))
+                .add_node(inner)
+                .add_edge(START, "first")
+                .add_edge("first", "inner")
+                .add_destinations("first", [END])
+                .add_edge("inner", END)
+                .configure_node(
+                    "first",
+                    node::NodeConfig {
+                        max_retries: 1,
+                        ..Default::default()
+                    },
+                );
+            graph.build()
+        };
+
+        assert_eq!(outer.graph_name(), "outer");
+        assert_eq!(outer.node_names(), vec!["first", "inner"]);
+        assert!(matches!(outer.edge("first"), Some(Edge::Direct(to)) if to == "inner"));
+        assert_eq!(outer.successors("first"), vec!["inner", END]);
+        assert_eq!(outer.predecessors("inner"), vec!["first"]);
+        assert_eq!(outer.predecessors("first"), vec![START]);
+        assert_eq!(outer.node_config("first").unwrap().max_retries, 1);
+        assert_eq!(outer.node_config("inner").unwrap().max_retries, 3);
+        assert!(outer.node_config("missing").is_none());
+
+        let visits: Vec<(Vec<&str>, &str, bool)> = outer
+            .walk()
+            .into_iter()
+            .map(|visit| (visit.path, visit.name, visit.is_subgraph))
+            .collect();
+        assert_eq!(
+            visits,
+            vec![
+                (vec!["outer"], "first", false),
+                (vec!["outer"], "inner", true),
+                (vec!["outer", "inner"], "leaf", false),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_batch() {
+        let node = FunctionNode::new("node1", |ctx: &Context, state: CounterState| {
+            let parent = ctx.parent_trace_id.clone();
+            async move {
+                if state.count < 0 {
+                    return Err(NodeError::Execution("negative".into()));
+                }
+                assert_eq!(parent.as_deref(), Some("batch"));
+                tokio::time::sleep(std::time::Duration::from_millis(
+                    10 * (5 - state.count as u64),
+                ))
+                .await;
+                Ok(NodeOutput::Updates(vec![CounterStateUpdate::Count(
+                    state.count * 10,
+                )]))
+            }
+        });
+
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(node)
+                .add_edge(START, "node1")
+                .add_edge("node1", END)
+                .configure_node(
+                    "node1",
+                    node::NodeConfig {
+                        max_retries: 1,
+                        ..Default::default()
+                    },
+                );
+            graph.build()
+        };
+
+        let ctx = Context::new("batch");
+        let inputs = vec![1, 2, -1, 3, 4]
+            .into_iter()
+            .map(|count| CounterState { count })
+            .collect();
+        let results = built_graph.run_batch(&ctx, inputs, 2).await;
+
+        assert_eq!(results.len(), 5);
+        let counts: Vec<Option<i32>> = results
+            .iter()
+            .map(|r| r.as_ref().ok().map(|s| s.count))
+            .collect();
+        assert_eq!(counts, vec![Some(10), Some(20), None, Some(30), Some(40)]);
+    }
+
+    #[tokio::test]
+    async fn test_io_graph() {
+        #[derive(State, Debug, Clone)]
+        struct DraftState {
+            #[update(replace)]
+            topic: String,
+            #[update(replace)]
+            attempts: u32,
+            #[update(replace)]
+            summary: String,
+        }
+
+        #[derive(schemars::JsonSchema)]
+        struct Request {
+            topic: String,
+        }
+
+        impl From<Request> for DraftState {
+            fn from(request: Request) -> Self {
+                DraftState {
+                    topic: request.topic,
+                    attempts: 0,
+                    summary: String::new(),
+                }
+            }
+        }
+
+        #[derive(Debug, PartialEq, schemars::JsonSchema)]
+        struct Summary {
+            summary: String,
+        }
+
+        impl From<DraftState> for Summary {
+            fn from(state: DraftState) -> Self {
+                Summary {
+                    summary: state.summary,
+                }
+            }
+        }
+
+        let write = FunctionNode::new("write", |_ctx, state: DraftState| async move {
+            Ok(NodeOutput::Updates(vec![
+                DraftStateUpdate::Attempts(state.attempts + 1),
+                DraftStateUpdate::Summary(format!("All about {}", state.topic)),
+            ]))
+        });
+        let mut graph = Graph::new("summarize");
+        graph
+            .add_node(write)
+            .add_edge(START, "write")
+            .add_edge("write", END);
+        let graph = graph.build().with_io::<Request, Summary>();
+
+        let ctx = Context::new("io");
+        let output = graph
+            .invoke(
+                &ctx,
+                Request {
+                    topic: "graphs".into(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            output,
+            Summary {
+                summary: "All about graphs".into()
+            }
+        );
+
+        let inputs = vec![Request { topic: "a".into() }, Request { topic: "b".into() }];
+        let outputs = graph.invoke_batch(&ctx, inputs, 2).await;
+        assert_eq!(outputs[1].as_ref().unwrap().summary, "All about b");
+
+        type SummaryGraph = IoGraph<DraftState, Request, Summary>;
+        let input_schema = SummaryGraph::input_schema();
+        assert!(input_schema["properties"].get("topic").is_some());
+        let output_schema = SummaryGraph::output_schema();
+        assert!(output_schema["properties"].get("attempts").is_none());
+    }
+
     // Test edge creation and debug formatting
     #[test]
     fn test_edge_creation_and_debug() {
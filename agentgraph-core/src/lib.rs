@@ -6,20 +6,34 @@ extern crate self as agentgraph_core;
 pub mod completion;
 pub mod graph;
 pub mod node;
+pub mod prebuilt;
+pub mod store;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 pub mod tool;
 pub mod types;
+pub mod update_log;
+
+// Re-exported for code generated by `#[derive(State)]`
+pub use schemars;
+pub use serde;
+pub use serde_json;
 
 pub mod prelude {
     //! Convenient re-exports of commonly used types
     pub use crate::completion::{
         ChatClient, ChatClientImpl, ChatCompletionCallOptions, ChatCompletionRequestOptions,
-        LangSmithTracer, TracingError, TracingProvider,
+        LangSmithTracer, ModelConfig, TracingError, TracingProvider,
     };
     pub use crate::graph::{Condition, Edge, Graph, END, START, Built, NotBuilt};
-    pub use crate::node::{Context, FunctionNode, MethodNode, Node};
-    pub use crate::tool::{JsonSchema, ToolFunction};
+    pub use crate::node::{BlockingNode, Context, FunctionNode, MethodNode, Node, RunConfig};
+    pub use crate::store::{InMemoryStore, Store};
+    pub use crate::tool::{
+        DynTool, GraphTool, GraphToolSpec, JsonSchema, ToolFunction, ToolRegistry,
+    };
     pub use crate::types::{
-        GraphError, GraphResult, GraphState, NodeError, NodeOutput, NodeResult, ToolError,
+        GraphError, GraphResult, GraphState, NodeError, NodeOutput, NodeResult, StoreError,
+        ToolError,
     };
 }
 
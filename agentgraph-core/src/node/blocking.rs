@@ -0,0 +1,91 @@
+use crate::node::Context;
+use crate::node::Node;
+use crate::types::{GraphState, NodeError, NodeResult};
+use async_trait::async_trait;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::fmt::Result;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A node that runs a synchronous function on Tokio's blocking thread pool,
+/// for CPU-heavy or blocking work that would otherwise stall the runtime.
+///
+/// A blocking thread cannot be stopped from outside, so when the graph stops
+/// waiting for the node (on timeout, or when the run is dropped) the
+/// function's `Context` is marked cancelled instead. Long-running functions
+/// should check `Context::is_cancelled` and return early.
+pub struct BlockingNode<S, F> {
+    name: String,
+    f: Arc<F>,
+    _phantom: std::marker::PhantomData<S>,
+}
+
+impl<S, F> BlockingNode<S, F>
+where
+    S: Debug + Send + Sync + GraphState,
+    S::Update: Send + 'static,
+    F: Fn(&Context, S) -> NodeResult<S> + Send + Sync + 'static,
+{
+    pub fn new(name: impl Into<String>, f: F) -> Self {
+        Self {
+            name: name.into(),
+            f: Arc::new(f),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<S, F> Node<S> for BlockingNode<S, F>
+where
+    S: Debug + Send + Sync + GraphState,
+    S::Update: Send + 'static,
+    F: Fn(&Context, S) -> NodeResult<S> + Send + Sync + 'static,
+{
+    async fn process(&self, ctx: &Context, state: S) -> NodeResult<S> {
+        let flag = CancellationFlag::default();
+        let _cancel_on_drop = CancelOnDrop(flag.clone());
+        let ctx = ctx.clone().with_extension(flag);
+        let f = self.f.clone();
+        tokio::task::spawn_blocking(move || f(&ctx, state))
+            .await
+            .map_err(|e| NodeError::Execution(format!("Node {} failed: {}", self.name, e)))?
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<S, F> Debug for BlockingNode<S, F> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("BlockingNode")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+/// Flag set once the graph stops waiting for a blocking node. Read it with
+/// `Context::is_cancelled`.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationFlag(Arc<AtomicBool>);
+
+impl CancellationFlag {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// Cancels the flag when the node's future completes or is dropped
+struct CancelOnDrop(CancellationFlag);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.cancel();
+    }
+}
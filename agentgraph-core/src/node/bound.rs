@@ -0,0 +1,64 @@
+use crate::node::{Context, Node};
+use crate::types::{GraphState, NodeResult};
+use async_trait::async_trait;
+use std::fmt::{Debug, Formatter, Result};
+use std::future::Future;
+use std::pin::Pin;
+
+/// Future returned by the function behind a `BoundNode`, which may borrow the
+/// instance and the context
+pub type BoundFuture<'a, S> = Pin<Box<dyn Future<Output = NodeResult<S>> + Send + 'a>>;
+
+/// A node that calls a function with a borrowed instance, as generated by the
+/// `#[node]` macro. Free functions use `()` as the instance.
+pub struct BoundNode<T, S: GraphState> {
+    name: String,
+    instance: T,
+    f: for<'a> fn(&'a T, &'a Context, S) -> BoundFuture<'a, S>,
+}
+
+impl<T, S> BoundNode<T, S>
+where
+    T: Send + Sync + 'static,
+    S: GraphState,
+{
+    pub fn new(
+        name: impl Into<String>,
+        instance: T,
+        f: for<'a> fn(&'a T, &'a Context, S) -> BoundFuture<'a, S>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            instance,
+            f,
+        }
+    }
+
+    /// The instance the node calls into
+    pub fn instance(&self) -> &T {
+        &self.instance
+    }
+}
+
+#[async_trait]
+impl<T, S> Node<S> for BoundNode<T, S>
+where
+    T: Send + Sync + 'static,
+    S: GraphState,
+{
+    async fn process(&self, ctx: &Context, state: S) -> NodeResult<S> {
+        (self.f)(&self.instance, ctx, state).await
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<T, S: GraphState> Debug for BoundNode<T, S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("BoundNode")
+            .field("name", &self.name)
+            .finish()
+    }
+}
@@ -1,4 +1,9 @@
+use super::{CancellationFlag, Extensions, RunConfig};
+use crate::store::Store;
+use crate::types::NodeError;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Context for node execution
 #[derive(Debug, Clone)]
@@ -9,6 +14,12 @@ pub struct Context {
     pub trace_id: String,
     /// Additional metadata
     pub metadata: HashMap<String, String>,
+    /// Long-term memory shared across runs and threads
+    pub store: Option<Arc<dyn Store>>,
+    /// Typed resources shared with nodes
+    pub extensions: Extensions,
+    /// Configurable parameters for this run
+    pub run_config: RunConfig,
 }
 
 impl Default for Context {
@@ -23,6 +34,9 @@ impl Context {
             parent_trace_id: None,
             trace_id: trace_id.into(),
             metadata: HashMap::new(),
+            store: None,
+            extensions: Extensions::new(),
+            run_config: RunConfig::new(),
         }
     }
 
@@ -36,11 +50,69 @@ impl Context {
         self
     }
 
+    pub fn with_store(mut self, store: Arc<dyn Store>) -> Self {
+        self.store = Some(store);
+        self
+    }
+
+    /// The shared store, if one was attached
+    pub fn store(&self) -> Option<&Arc<dyn Store>> {
+        self.store.as_ref()
+    }
+
+    /// Attach a typed resource, replacing any previous one of the same type
+    pub fn with_extension<T: Send + Sync + 'static>(mut self, value: T) -> Self {
+        self.extensions.insert(value);
+        self
+    }
+
+    /// Attach an already shared typed resource
+    pub fn with_extension_arc<T: Send + Sync + 'static>(mut self, value: Arc<T>) -> Self {
+        self.extensions.insert_arc(value);
+        self
+    }
+
+    pub fn insert_extension<T: Send + Sync + 'static>(&mut self, value: T) {
+        self.extensions.insert(value);
+    }
+
+    /// Borrow the typed resource of type `T`
+    pub fn extension<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.extensions.get()
+    }
+
+    /// Shared handle to the typed resource of type `T`
+    pub fn extension_arc<T: Send + Sync + 'static>(&self) -> Option<Arc<T>> {
+        self.extensions.get_arc()
+    }
+
+    /// Whether the graph has stopped waiting for this node. Only set for
+    /// `BlockingNode` functions; async nodes are simply dropped.
+    pub fn is_cancelled(&self) -> bool {
+        self.extension::<CancellationFlag>()
+            .is_some_and(|flag| flag.is_cancelled())
+    }
+
+    pub fn with_run_config(mut self, run_config: RunConfig) -> Self {
+        self.run_config = run_config;
+        self
+    }
+
+    /// Read the run configuration as the typed config `C`
+    pub fn config<C: DeserializeOwned>(&self) -> Result<C, NodeError> {
+        self.run_config
+            .parse()
+            .map_err(|e| NodeError::Execution(format!("Invalid run config: {}", e)))
+    }
+
     pub fn next_node_context(&self) -> Self {
         Self {
             parent_trace_id: Some(self.trace_id.clone()),
             trace_id: uuid::Uuid::new_v4().to_string(),
             metadata: self.metadata.clone(),
+            store: self.store.clone(),
+            extensions: self.extensions.clone(),
+            run_config: self.run_config.clone(),
         }
     }
 }
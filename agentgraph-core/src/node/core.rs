@@ -1,4 +1,4 @@
-use crate::{Context, GraphState, NodeResult};
+use crate::{Built, Context, Graph, GraphState, NodeResult};
 use async_trait::async_trait;
 use std::fmt::{Debug, Result, Formatter};
 
@@ -14,6 +14,11 @@ where
     /// Get the name of this node
     fn name(&self) -> &str;
 
+    /// The graph behind this node, if it is a subgraph
+    fn subgraph(&self) -> Option<&Graph<S, Built>> {
+        None
+    }
+
     fn debug_node(&self, f: &mut Formatter<'_>) -> Result {
         write!(f, "Node({})", self.name())
     }
@@ -1,12 +1,20 @@
+mod blocking;
+mod bound;
 mod config;
 mod context;
 mod core;
+mod extensions;
 mod function;
 mod method;
+mod run_config;
 mod tests;
 
+pub use blocking::{BlockingNode, CancellationFlag};
+pub use bound::{BoundFuture, BoundNode};
 pub use config::NodeConfig;
 pub use context::Context;
 pub use core::Node;
+pub use extensions::Extensions;
 pub use function::FunctionNode;
-pub use method::MethodNode;
\ No newline at end of file
+pub use method::MethodNode;
+pub use run_config::RunConfig;
@@ -0,0 +1,73 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Configurable parameters for a single run, such as the model name or
+/// temperature. Values are stored as JSON and read back as typed structs with
+/// `Context::config`, so one compiled graph can serve different settings.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct RunConfig {
+    values: BTreeMap<String, Value>,
+}
+
+impl RunConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set a value, replacing any previous one.
+    ///
+    /// Panics if the value cannot be serialized, like `serde_json::json!`.
+    /// Use `set` to handle the error instead.
+    pub fn with(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        let key = key.into();
+        if let Err(e) = self.set(key.clone(), value) {
+            panic!("Run config value {} cannot be serialized: {}", key, e);
+        }
+        self
+    }
+
+    /// Set a value, replacing any previous one. A value that cannot be
+    /// serialized is not stored and any previous value is kept.
+    pub fn set(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Serialize,
+    ) -> Result<(), serde_json::Error> {
+        let value = serde_json::to_value(value)?;
+        self.values.insert(key.into(), value);
+        Ok(())
+    }
+
+    /// Read a single value as `T`
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.values
+            .get(key)
+            .and_then(|value| serde_json::from_value(value.clone()).ok())
+    }
+
+    /// Set every value of `other`, replacing any previous ones
+    pub fn merge(&mut self, other: RunConfig) {
+        self.values.extend(other.values);
+    }
+
+    pub fn get_value(&self, key: &str) -> Option<&Value> {
+        self.values.get(key)
+    }
+
+    /// Read all values as the typed config `C`
+    pub fn parse<C: DeserializeOwned>(&self) -> Result<C, serde_json::Error> {
+        let values = self
+            .values
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        serde_json::from_value(Value::Object(values))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
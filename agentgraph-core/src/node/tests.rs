@@ -1,7 +1,8 @@
 #[cfg(test)]
 mod tests {
     use agentgraph_core::*;
-    use agentgraph_macros::State;
+    use agentgraph_macros::{node, State};
+    use std::sync::Arc;
 
     #[derive(State, Debug, Clone, PartialEq)]
     struct TestState {
@@ -40,8 +41,222 @@ mod tests {
             NodeOutput::Updates(updates) => {
                 panic!("Expected a full state, but got updates: {:?}", updates);
             }
+            NodeOutput::Command { updates, goto } => {
+                panic!(
+                    "Expected a full state, but got command to {}: {:?}",
+                    goto, updates
+                );
+            }
         }
 
         assert_eq!(node.name(), "test");
     }
+
+    #[derive(Debug)]
+    struct Greeter {
+        greeting: String,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct UserId(u64);
+
+    #[tokio::test]
+    async fn test_context_extensions() {
+        let node = FunctionNode::new("greet", |ctx: &Context, _: TestState| {
+            let greeter = ctx.extension_arc::<Greeter>();
+            let user = ctx.extension::<UserId>().cloned();
+            async move {
+                let greeter = greeter.ok_or_else(|| NodeError::Execution("no greeter".into()))?;
+                let user = user.ok_or_else(|| NodeError::Execution("no user".into()))?;
+                Ok(NodeOutput::Updates(vec![TestStateUpdate::Name(format!(
+                    "{} {}",
+                    greeter.greeting, user.0
+                ))]))
+            }
+        });
+
+        let ctx = Context::new("test")
+            .with_extension(Greeter {
+                greeting: "hello".to_string(),
+            })
+            .with_extension(UserId(1))
+            .with_extension(UserId(42));
+
+        // Extensions are inherited by child contexts
+        let child = ctx.next_node_context();
+        assert_eq!(child.extensions.len(), 2);
+        assert_eq!(child.extension::<UserId>(), Some(&UserId(42)));
+        assert!(child.extension::<String>().is_none());
+
+        let result = node
+            .process(
+                &child,
+                TestState {
+                    name: "test".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        match result {
+            NodeOutput::Updates(updates) => {
+                assert!(matches!(&updates[..], [TestStateUpdate::Name(name)] if name == "hello 42"))
+            }
+            _ => panic!("Expected updates"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_config() {
+        let defaults = ChatCompletionRequestOptions::default();
+        let node = FunctionNode::new("model", move |ctx: &Context, _: TestState| {
+            let config = ctx.config::<ModelConfig>();
+            let defaults = defaults.clone();
+            async move {
+                let options = config?.apply(&defaults);
+                Ok(NodeOutput::Updates(vec![TestStateUpdate::Name(format!(
+                    "{}@{:?}",
+                    options.model, options.temperature
+                ))]))
+            }
+        });
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(node)
+                .add_edge(START, "model")
+                .add_edge("model", END)
+                .set_config_schema::<ModelConfig>();
+            graph.build()
+        };
+        let state = TestState {
+            name: "test".to_string(),
+        };
+
+        // Defaults apply when nothing is configured
+        let ctx = Context::new("test");
+        let result = built_graph.run(&ctx, state.clone()).await.unwrap();
+        assert_eq!(result.name, "gpt-4o-mini@Some(0.0)");
+
+        let ctx = Context::new("test").with_run_config(
+            RunConfig::new()
+                .with("model", "gpt-4o")
+                .with("temperature", 0.5),
+        );
+        assert_eq!(ctx.run_config.get::<String>("model").unwrap(), "gpt-4o");
+        let result = built_graph.run(&ctx, state.clone()).await.unwrap();
+        assert_eq!(result.name, "gpt-4o@Some(0.5)");
+
+        // Values of the wrong type are reported as node errors
+        let ctx = Context::new("test").with_run_config(RunConfig::new().with("temperature", "hot"));
+        assert!(built_graph.run(&ctx, state).await.is_err());
+
+        // Values that cannot be serialized are rejected, not stored as null
+        let mut run_config = RunConfig::new().with("model", "gpt-4o");
+        let unserializable = std::collections::HashMap::from([((1, 2), 3)]);
+        assert!(run_config.set("model", unserializable).is_err());
+        assert_eq!(run_config.get::<String>("model").unwrap(), "gpt-4o");
+
+        let schema = built_graph.config_schema().unwrap();
+        assert!(schema["properties"]["system_prompt"].is_object());
+    }
+
+    #[node]
+    async fn shout(_ctx: &Context, state: TestState) -> NodeResult<TestState> {
+        Ok(NodeOutput::Updates(vec![TestStateUpdate::Name(
+            state.name.to_uppercase(),
+        )]))
+    }
+
+    struct Announcer {
+        greeting: String,
+    }
+
+    impl Announcer {
+        #[node(name = "greet")]
+        async fn call(&self, ctx: &Context, state: TestState) -> NodeResult<TestState> {
+            Ok(NodeOutput::Updates(vec![TestStateUpdate::Name(format!(
+                "{} {} ({})",
+                self.greeting, state.name, ctx.trace_id
+            ))]))
+        }
+
+        #[node]
+        async fn sign(&self, _ctx: &Context, state: TestState) -> NodeResult<TestState> {
+            Ok(NodeOutput::Updates(vec![TestStateUpdate::Name(format!(
+                "{}, {}",
+                state.name, self.greeting
+            ))]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_node_macro() {
+        // One instance backs both of its method nodes
+        let announcer = Arc::new(Announcer {
+            greeting: "hello".to_string(),
+        });
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(shout_node())
+                .add_node(announcer.clone().call_node())
+                .add_node(announcer.sign_node())
+                .add_edge(START, "shout")
+                .add_edge("shout", "greet")
+                .add_edge("greet", "sign")
+                .add_edge("sign", END);
+            graph.build()
+        };
+        assert_eq!(built_graph.node_names(), ["greet", "shout", "sign"]);
+
+        let ctx = Context::new("trace");
+        let state = TestState {
+            name: "ryan".to_string(),
+        };
+        let result = built_graph.run(&ctx, state).await.unwrap();
+        assert_eq!(result.name, "hello RYAN (trace), hello");
+    }
+
+    #[tokio::test]
+    async fn test_blocking_node() {
+        let ctx = Context::new("test");
+        let state = TestState {
+            name: "ryan".to_string(),
+        };
+
+        let node = BlockingNode::new("upper", |_ctx: &Context, state: TestState| {
+            Ok(NodeOutput::Full(TestState {
+                name: state.name.to_uppercase(),
+            }))
+        });
+        match node.process(&ctx, state.clone()).await.unwrap() {
+            NodeOutput::Full(state) => assert_eq!(state.name, "RYAN"),
+            _ => panic!("Expected full state"),
+        }
+
+        let node = BlockingNode::new("panics", |_ctx: &Context, _: TestState| panic!("boom"));
+        assert!(matches!(
+            node.process(&ctx, state.clone()).await,
+            Err(NodeError::Execution(_))
+        ));
+
+        // The function is told once the caller stops waiting for it
+        let (done, finished) = std::sync::mpsc::channel();
+        let node = BlockingNode::new("slow", move |ctx: &Context, state: TestState| {
+            while !ctx.is_cancelled() {
+                std::thread::sleep(std::time::Duration::from_millis(5));
+            }
+            done.send(()).unwrap();
+            Ok(NodeOutput::Full(state))
+        });
+        let result = tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            node.process(&ctx, state),
+        )
+        .await;
+        assert!(result.is_err());
+        finished
+            .recv_timeout(std::time::Duration::from_secs(5))
+            .unwrap();
+    }
 }
@@ -0,0 +1,130 @@
+use super::HasMessages;
+use crate::completion::{
+    ChatClient, ChatCompletionCallOptions, ChatCompletionRequestOptions, ModelConfig,
+};
+use crate::node::{Context, Node};
+use crate::types::{NodeError, NodeOutput, NodeResult};
+use async_openai::types::{
+    ChatCompletionRequestAssistantMessageArgs, ChatCompletionRequestMessage,
+    ChatCompletionRequestSystemMessageArgs,
+};
+use async_trait::async_trait;
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Node that sends the chat history to a `ChatClient` and appends the
+/// assistant reply, tool calls included.
+///
+/// `ModelConfig` values in the run config override the node's model,
+/// temperature and system prompt. The system prompt is sent with every
+/// request but never stored in the state.
+pub struct ChatNode<S> {
+    name: String,
+    client: Arc<dyn ChatClient>,
+    options: ChatCompletionRequestOptions,
+    system_prompt: Option<String>,
+    max_messages: Option<usize>,
+    _state: PhantomData<fn() -> S>,
+}
+
+impl<S: HasMessages> ChatNode<S> {
+    /// A node named "agent"
+    pub fn new(client: Arc<dyn ChatClient>, options: ChatCompletionRequestOptions) -> Self {
+        Self {
+            name: "agent".to_string(),
+            client,
+            options,
+            system_prompt: None,
+            max_messages: None,
+            _state: PhantomData,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Send only the latest `max_messages` non-system messages, see
+    /// `trim_messages`. The state keeps the full history.
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    pub fn options(&self) -> &ChatCompletionRequestOptions {
+        &self.options
+    }
+}
+
+#[async_trait]
+impl<S: HasMessages> Node<S> for ChatNode<S> {
+    async fn process(&self, ctx: &Context, state: S) -> NodeResult<S> {
+        let config: ModelConfig = ctx.config()?;
+        let options = config.apply(&self.options);
+
+        let mut messages = vec![];
+        if let Some(system_prompt) = config.system_prompt_or(self.system_prompt.as_deref()) {
+            messages.push(
+                ChatCompletionRequestSystemMessageArgs::default()
+                    .content(system_prompt)
+                    .build()?
+                    .into(),
+            );
+        }
+        match self.max_messages {
+            Some(max_messages) => messages.extend(state.trimmed_messages(max_messages)),
+            None => messages.extend(state.messages().into_iter().cloned()),
+        }
+
+        let request = self
+            .client
+            .create_chat_completion_request(messages, &options)
+            .map_err(|e| NodeError::Execution(e.to_string()))?;
+        let response = self
+            .client
+            .complete(
+                request,
+                Some(ChatCompletionCallOptions::new(
+                    Some(ctx.trace_id.clone()),
+                    ctx.parent_trace_id.clone(),
+                )),
+            )
+            .await
+            .map_err(|e| NodeError::ModelError(e.to_string()))?;
+
+        let choice = response
+            .choices
+            .into_iter()
+            .next()
+            .ok_or_else(|| NodeError::ModelError("No response choices".to_string()))?;
+        let mut assistant = ChatCompletionRequestAssistantMessageArgs::default();
+        assistant.content(choice.message.content.unwrap_or_default());
+        if let Some(tool_calls) = choice.message.tool_calls.filter(|calls| !calls.is_empty()) {
+            assistant.tool_calls(tool_calls);
+        }
+        let messages = vec![ChatCompletionRequestMessage::Assistant(assistant.build()?)];
+        Ok(NodeOutput::Updates(vec![S::append_messages(messages)]))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<S> Debug for ChatNode<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ChatNode")
+            .field("name", &self.name)
+            .field("model", &self.options.model)
+            .field("system_prompt", &self.system_prompt)
+            .field("max_messages", &self.max_messages)
+            .finish()
+    }
+}
@@ -0,0 +1,51 @@
+use super::HasMessages;
+use crate::types::GraphState;
+use async_openai::types::ChatCompletionRequestMessage;
+use serde::{Deserialize, Serialize};
+
+/// Graph state holding nothing but a chat history, as used by the prebuilt
+/// ReAct agent. States that need more fields can derive `HasMessages`
+/// instead, with `#[state(messages)]` on their history field.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MessagesState {
+    pub messages: Vec<ChatCompletionRequestMessage>,
+}
+
+/// Updates to a `MessagesState`
+#[derive(Debug, Clone)]
+pub enum MessagesStateUpdate {
+    /// Append messages to the history
+    Messages(Vec<ChatCompletionRequestMessage>),
+}
+
+impl MessagesState {
+    pub fn new(messages: Vec<ChatCompletionRequestMessage>) -> Self {
+        Self { messages }
+    }
+}
+
+impl From<Vec<ChatCompletionRequestMessage>> for MessagesState {
+    fn from(messages: Vec<ChatCompletionRequestMessage>) -> Self {
+        Self::new(messages)
+    }
+}
+
+impl GraphState for MessagesState {
+    type Update = MessagesStateUpdate;
+
+    fn apply(&mut self, update: Self::Update) {
+        match update {
+            MessagesStateUpdate::Messages(messages) => self.messages.extend(messages),
+        }
+    }
+}
+
+impl HasMessages for MessagesState {
+    fn messages(&self) -> Vec<&ChatCompletionRequestMessage> {
+        self.messages.iter().collect()
+    }
+
+    fn append_messages(messages: Vec<ChatCompletionRequestMessage>) -> MessagesStateUpdate {
+        MessagesStateUpdate::Messages(messages)
+    }
+}
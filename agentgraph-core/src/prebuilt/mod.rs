@@ -0,0 +1,165 @@
+//! Ready-made nodes and graphs for chat agents.
+//!
+//! The nodes work with any state that exposes its chat history through
+//! `HasMessages`.
+
+mod chat_node;
+mod messages_state;
+mod react;
+mod tests;
+mod tool_node;
+
+use crate::graph::END;
+use crate::types::GraphState;
+use async_openai::types::{
+    ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessage, Role,
+};
+use std::borrow::Borrow;
+
+pub use async_openai::types::ChatCompletionRequestMessage;
+
+pub use chat_node::ChatNode;
+pub use messages_state::{MessagesState, MessagesStateUpdate};
+pub use react::{create_react_agent, ReactAgent};
+pub use tool_node::ToolNode;
+
+/// States holding a chat history that prebuilt nodes read and append to.
+///
+/// Implement it by hand or with `#[state(messages)]` on a field of a
+/// `#[derive(State)]` struct: either an appended
+/// `Vec<ChatCompletionRequestMessage>` or a `Vec<Message>` using the
+/// `messages` strategy.
+pub trait HasMessages: GraphState {
+    /// The chat history, oldest first
+    fn messages(&self) -> Vec<&ChatCompletionRequestMessage>;
+
+    /// Update appending `messages` to the history
+    fn append_messages(messages: Vec<ChatCompletionRequestMessage>) -> Self::Update;
+
+    fn last_message(&self) -> Option<&ChatCompletionRequestMessage> {
+        self.messages().last().copied()
+    }
+
+    fn last_assistant_message(&self) -> Option<&ChatCompletionRequestAssistantMessage> {
+        self.messages()
+            .into_iter()
+            .rev()
+            .find_map(|message| match message {
+                ChatCompletionRequestMessage::Assistant(assistant) => Some(assistant),
+                _ => None,
+            })
+    }
+
+    /// Tool calls of the latest assistant message not answered yet
+    fn pending_tool_calls(&self) -> Vec<ChatCompletionMessageToolCall> {
+        pending_tool_calls(&self.messages())
+    }
+
+    fn has_pending_tool_calls(&self) -> bool {
+        !self.pending_tool_calls().is_empty()
+    }
+
+    /// Messages sent with `role`, oldest first
+    fn messages_with_role(&self, role: Role) -> Vec<&ChatCompletionRequestMessage> {
+        self.messages()
+            .into_iter()
+            .filter(|message| message_role(message) == role)
+            .collect()
+    }
+
+    /// The history cut down with `trim_messages`
+    fn trimmed_messages(&self, max_messages: usize) -> Vec<ChatCompletionRequestMessage> {
+        trim_messages(&self.messages(), max_messages)
+    }
+}
+
+/// Role of the author of a message
+pub fn message_role(message: &ChatCompletionRequestMessage) -> Role {
+    match message {
+        ChatCompletionRequestMessage::System(_) => Role::System,
+        ChatCompletionRequestMessage::User(_) => Role::User,
+        ChatCompletionRequestMessage::Assistant(_) => Role::Assistant,
+        ChatCompletionRequestMessage::Tool(_) => Role::Tool,
+        ChatCompletionRequestMessage::Function(_) => Role::Function,
+    }
+}
+
+/// System messages followed by the `max_messages` most recent other
+/// messages, in their original order. Tool messages are never cut from the
+/// assistant message that called them, since models reject them on their
+/// own, so a turn ending in tool results is kept whole even if that exceeds
+/// `max_messages`.
+pub fn trim_messages<M: Borrow<ChatCompletionRequestMessage>>(
+    messages: &[M],
+    max_messages: usize,
+) -> Vec<ChatCompletionRequestMessage> {
+    let is_system =
+        |message: &M| matches!(message.borrow(), ChatCompletionRequestMessage::System(_));
+    let others: Vec<usize> = (0..messages.len())
+        .filter(|&index| !is_system(&messages[index]))
+        .collect();
+    let mut start = others.len().saturating_sub(max_messages);
+    while start > 0
+        && start < others.len()
+        && matches!(
+            messages[others[start]].borrow(),
+            ChatCompletionRequestMessage::Tool(_)
+        )
+    {
+        start -= 1;
+    }
+    let kept = &others[start..];
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(index, message)| is_system(message) || kept.contains(index))
+        .map(|(_, message)| message.borrow().clone())
+        .collect()
+}
+
+/// Tool calls of the latest assistant message that have no tool message
+/// answering them yet
+pub fn pending_tool_calls<M: Borrow<ChatCompletionRequestMessage>>(
+    messages: &[M],
+) -> Vec<ChatCompletionMessageToolCall> {
+    let Some((index, assistant)) = last_assistant(messages) else {
+        return vec![];
+    };
+    let answered: Vec<&str> = messages[index + 1..]
+        .iter()
+        .filter_map(|message| match message.borrow() {
+            ChatCompletionRequestMessage::Tool(tool) => Some(tool.tool_call_id.as_str()),
+            _ => None,
+        })
+        .collect();
+    assistant
+        .tool_calls
+        .iter()
+        .flatten()
+        .filter(|call| !answered.contains(&call.id.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// Router for the node after a model node: `tools_node` when the latest
+/// assistant message has pending tool calls, otherwise `END`
+pub fn tools_condition<S: HasMessages>(state: &S, tools_node: &str) -> String {
+    if state.has_pending_tool_calls() {
+        tools_node.to_string()
+    } else {
+        END.to_string()
+    }
+}
+
+fn last_assistant<M: Borrow<ChatCompletionRequestMessage>>(
+    messages: &[M],
+) -> Option<(usize, &ChatCompletionRequestAssistantMessage)> {
+    messages
+        .iter()
+        .enumerate()
+        .rev()
+        .find_map(|(index, message)| match message.borrow() {
+            ChatCompletionRequestMessage::Assistant(assistant) => Some((index, assistant)),
+            _ => None,
+        })
+}
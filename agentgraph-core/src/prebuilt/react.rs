@@ -0,0 +1,118 @@
+use super::{tools_condition, ChatNode, HasMessages, MessagesState, ToolNode};
+use crate::completion::{ChatClient, ChatCompletionRequestOptions};
+use crate::graph::{Built, Graph, END, START};
+use crate::tool::ToolRegistry;
+use async_openai::types::ChatCompletionRequestMessage;
+use std::sync::Arc;
+
+/// Builder for a tool-calling agent graph: the model node "agent" runs, its
+/// tool calls go to the node "tools", and the results go back to the model
+/// until it answers without calling tools or runs out of steps.
+///
+/// Tool calls are always answered before the run ends, so the history can be
+/// sent to the model again.
+#[derive(Clone)]
+pub struct ReactAgent {
+    client: Arc<dyn ChatClient>,
+    options: ChatCompletionRequestOptions,
+    tools: ToolRegistry,
+    system_prompt: Option<String>,
+    max_steps: usize,
+    max_messages: Option<usize>,
+}
+
+impl ReactAgent {
+    /// An agent without tools, allowed 10 model calls per user message
+    pub fn new(client: Arc<dyn ChatClient>, options: ChatCompletionRequestOptions) -> Self {
+        Self {
+            client,
+            options,
+            tools: ToolRegistry::new(),
+            system_prompt: None,
+            max_steps: 10,
+            max_messages: None,
+        }
+    }
+
+    /// Tools the model may call. Their schemas replace `options.tools`.
+    pub fn with_tools(mut self, tools: ToolRegistry) -> Self {
+        self.tools = tools;
+        self
+    }
+
+    pub fn with_system_prompt(mut self, system_prompt: impl Into<String>) -> Self {
+        self.system_prompt = Some(system_prompt.into());
+        self
+    }
+
+    /// Maximum number of model calls since the latest user message. Once it
+    /// is reached the run ends after the tools of the last reply have run,
+    /// without calling the model again.
+    pub fn with_max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps.max(1);
+        self
+    }
+
+    /// Send the model only the latest `max_messages` non-system messages
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = Some(max_messages);
+        self
+    }
+
+    pub fn build(self) -> Graph<MessagesState, Built> {
+        let mut options = self.options;
+        if !self.tools.is_empty() {
+            options.tools = Some(self.tools.schemas());
+        }
+        let mut agent = ChatNode::new(self.client, options);
+        if let Some(system_prompt) = self.system_prompt {
+            agent = agent.with_system_prompt(system_prompt);
+        }
+        if let Some(max_messages) = self.max_messages {
+            agent = agent.with_max_messages(max_messages);
+        }
+        let max_steps = self.max_steps;
+
+        let mut graph = Graph::new("react_agent");
+        graph.add_node(agent);
+        graph.add_node(ToolNode::new(self.tools));
+        graph.add_edge(START, "agent");
+        graph.add_conditional_edge("agent", |state: &MessagesState| {
+            tools_condition(state, "tools")
+        });
+        // The limit is checked before the model step, so pending tool calls
+        // are never left unanswered
+        graph.add_conditional_edge("tools", move |state: &MessagesState| {
+            if model_steps(&state.messages()) >= max_steps {
+                END.to_string()
+            } else {
+                "agent".to_string()
+            }
+        });
+        graph.build()
+    }
+}
+
+/// Build a ReAct agent graph in one call, see `ReactAgent`
+pub fn create_react_agent(
+    client: Arc<dyn ChatClient>,
+    options: ChatCompletionRequestOptions,
+    tools: ToolRegistry,
+    system_prompt: Option<String>,
+) -> Graph<MessagesState, Built> {
+    let mut agent = ReactAgent::new(client, options).with_tools(tools);
+    if let Some(system_prompt) = system_prompt {
+        agent = agent.with_system_prompt(system_prompt);
+    }
+    agent.build()
+}
+
+// Number of assistant messages after the latest user message
+fn model_steps(messages: &[&ChatCompletionRequestMessage]) -> usize {
+    messages
+        .iter()
+        .rev()
+        .take_while(|message| !matches!(message, ChatCompletionRequestMessage::User(_)))
+        .filter(|message| matches!(message, ChatCompletionRequestMessage::Assistant(_)))
+        .count()
+}
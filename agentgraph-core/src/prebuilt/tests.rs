@@ -0,0 +1,385 @@
+#[cfg(test)]
+mod tests {
+    use crate::completion::{
+        ChatClient, ChatCompletionCallOptions, ChatCompletionRequestOptions, Message,
+    };
+    use crate::node::RunConfig;
+    use crate::prebuilt::{
+        create_react_agent, tools_condition, ChatNode, HasMessages, MessagesState, ToolNode,
+    };
+    use crate::tool::{ChatCompletionToolType, ToolRegistry};
+    use crate::*;
+    use agentgraph_macros::State;
+    use async_openai::types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessageArgs,
+        ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
+        ChatCompletionRequestToolMessageArgs, ChatCompletionRequestToolMessageContent,
+        ChatCompletionRequestUserMessage, CreateChatCompletionRequest,
+        CreateChatCompletionResponse, CreateChatCompletionStreamResponse, FunctionCall, Role,
+    };
+    use async_trait::async_trait;
+    use futures::Stream;
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+    use std::error::Error;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(State, Debug, Clone, Default)]
+    struct ChatState {
+        #[update(append)]
+        #[state(messages)]
+        messages: Vec<ChatCompletionRequestMessage>,
+    }
+
+    #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+    struct EchoParams {
+        text: String,
+    }
+
+    struct Echo;
+
+    #[async_trait]
+    impl ToolFunction for Echo {
+        type Params = EchoParams;
+        type Response = String;
+
+        fn name() -> &'static str {
+            "echo"
+        }
+
+        fn description() -> &'static str {
+            "Echoes the text back"
+        }
+
+        async fn execute(&self, params: EchoParams) -> Result<String, ToolError> {
+            Ok(params.text)
+        }
+    }
+
+    fn tool_call(id: &str, name: &str, arguments: &str) -> ChatCompletionMessageToolCall {
+        ChatCompletionMessageToolCall {
+            id: id.to_string(),
+            r#type: ChatCompletionToolType::Function,
+            function: FunctionCall {
+                name: name.to_string(),
+                arguments: arguments.to_string(),
+            },
+        }
+    }
+
+    fn tool_results(messages: &[ChatCompletionRequestMessage]) -> Vec<(String, String)> {
+        messages
+            .iter()
+            .filter_map(|message| match message {
+                ChatCompletionRequestMessage::Tool(tool) => match &tool.content {
+                    ChatCompletionRequestToolMessageContent::Text(text) => {
+                        Some((tool.tool_call_id.clone(), text.clone()))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_tool_node() {
+        let assistant = ChatCompletionRequestAssistantMessageArgs::default()
+            .tool_calls(vec![
+                tool_call("1", "echo", r#"{"text": "hi"}"#),
+                tool_call("2", "missing", "{}"),
+                tool_call("3", "echo", "not json"),
+            ])
+            .build()
+            .unwrap();
+        let mut state = ChatState {
+            messages: vec![
+                ChatCompletionRequestUserMessage::from("go").into(),
+                assistant.into(),
+            ],
+        };
+
+        let node = ToolNode::new(ToolRegistry::new().with(Echo)).with_concurrency(2);
+        let ctx = Context::new("test");
+        match node.process(&ctx, state.clone()).await.unwrap() {
+            NodeOutput::Updates(updates) => state.apply_many(updates),
+            _ => panic!("Expected updates"),
+        }
+
+        let results = tool_results(&state.messages);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], ("1".to_string(), "\"hi\"".to_string()));
+        assert_eq!(results[1].1, "Error: Tool not found: missing");
+        assert!(results[2].1.starts_with("Error: Schema:"));
+
+        // Answered calls are not run again
+        match node.process(&ctx, state.clone()).await.unwrap() {
+            NodeOutput::Updates(updates) => assert!(updates.is_empty()),
+            _ => panic!("Expected updates"),
+        }
+    }
+
+    type BoxError = Box<dyn Error + Send + Sync>;
+
+    // Chat client that asks for one echo call and records what it was sent
+    #[derive(Default)]
+    struct ScriptedClient {
+        requests: Mutex<
+            Vec<(
+                CreateChatCompletionRequest,
+                Option<ChatCompletionCallOptions>,
+            )>,
+        >,
+    }
+
+    #[async_trait]
+    impl ChatClient for ScriptedClient {
+        fn create_chat_completion_request(
+            &self,
+            messages: Vec<ChatCompletionRequestMessage>,
+            options: &ChatCompletionRequestOptions,
+        ) -> Result<CreateChatCompletionRequest, BoxError> {
+            Ok(CreateChatCompletionRequest {
+                model: options.model.clone(),
+                messages,
+                temperature: options.temperature,
+                ..Default::default()
+            })
+        }
+
+        fn create_chat_completion_stream_request(
+            &self,
+            messages: Vec<ChatCompletionRequestMessage>,
+            options: &ChatCompletionRequestOptions,
+        ) -> Result<CreateChatCompletionRequest, BoxError> {
+            self.create_chat_completion_request(messages, options)
+        }
+
+        async fn complete(
+            &self,
+            request: CreateChatCompletionRequest,
+            options: Option<ChatCompletionCallOptions>,
+        ) -> Result<CreateChatCompletionResponse, BoxError> {
+            let model = request.model.clone();
+            self.requests.lock().unwrap().push((request, options));
+            Ok(serde_json::from_value(json!({
+                "id": "test",
+                "object": "chat.completion",
+                "created": 0,
+                "model": model,
+                "choices": [{
+                    "index": 0,
+                    "message": {
+                        "role": "assistant",
+                        "content": null,
+                        "tool_calls": [{
+                            "id": "call_1",
+                            "type": "function",
+                            "function": { "name": "echo", "arguments": "{\"text\": \"hi\"}" }
+                        }]
+                    },
+                    "finish_reason": "tool_calls"
+                }]
+            }))?)
+        }
+
+        async fn complete_stream(
+            &self,
+            _request: CreateChatCompletionRequest,
+            _options: Option<ChatCompletionCallOptions>,
+        ) -> Result<
+            Pin<
+                Box<dyn Stream<Item = Result<CreateChatCompletionStreamResponse, BoxError>> + Send>,
+            >,
+            BoxError,
+        > {
+            Err("streaming is not supported".into())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_node() {
+        let client = Arc::new(ScriptedClient::default());
+        let node: ChatNode<ChatState> = ChatNode::new(
+            client.clone(),
+            ChatCompletionRequestOptions {
+                model: "default-model".to_string(),
+                temperature: Some(0.0),
+                tools: None,
+                tool_choice: None,
+            },
+        )
+        .with_system_prompt("Be brief");
+        assert_eq!(Node::<ChatState>::name(&node), "agent");
+
+        let mut state = ChatState {
+            messages: vec![ChatCompletionRequestUserMessage::from("go").into()],
+        };
+        let ctx = Context::new("trace").with_parent_trace_id("parent");
+        match node.process(&ctx, state.clone()).await.unwrap() {
+            NodeOutput::Updates(updates) => state.apply_many(updates),
+            _ => panic!("Expected updates"),
+        }
+
+        // The assistant reply keeps its tool calls, the system prompt is not stored
+        assert_eq!(state.messages.len(), 2);
+        let calls = crate::prebuilt::pending_tool_calls(&state.messages);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].function.name, "echo");
+
+        {
+            let requests = client.requests.lock().unwrap();
+            let (request, options) = &requests[0];
+            assert_eq!(request.model, "default-model");
+            assert_eq!(request.messages.len(), 2);
+            assert!(matches!(
+                request.messages[0],
+                ChatCompletionRequestMessage::System(_)
+            ));
+            let options = options.as_ref().unwrap();
+            assert_eq!(options.trace_id.as_deref(), Some("trace"));
+            assert_eq!(options.parent_trace_id.as_deref(), Some("parent"));
+        }
+
+        // Run config overrides the node defaults
+        let ctx =
+            Context::new("trace").with_run_config(RunConfig::new().with("model", "override-model"));
+        node.process(&ctx, state.clone()).await.unwrap();
+        let requests = client.requests.lock().unwrap();
+        assert_eq!(requests[1].0.model, "override-model");
+    }
+
+    #[tokio::test]
+    async fn test_react_agent() {
+        let client = Arc::new(ScriptedClient::default());
+        let graph = create_react_agent(
+            client.clone(),
+            ChatCompletionRequestOptions {
+                model: "default-model".to_string(),
+                temperature: None,
+                tools: None,
+                tool_choice: None,
+            },
+            ToolRegistry::new().with(Echo),
+            Some("Use the tools".to_string()),
+        );
+
+        let state = MessagesState::new(vec![ChatCompletionRequestUserMessage::from("go").into()]);
+        let state = graph.run(&Context::new("test"), state).await.unwrap();
+
+        // The scripted model always calls a tool, so the step limit ends the
+        // run, but only once the last call has been answered
+        let requests = client.requests.lock().unwrap();
+        assert_eq!(requests.len(), 10);
+        assert_eq!(requests[0].0.messages.len(), 2);
+        assert_eq!(state.messages.len(), 21);
+        assert_eq!(tool_results(&state.messages).len(), 10);
+        assert!(!state.has_pending_tool_calls());
+    }
+
+    #[test]
+    fn test_messages_helpers() {
+        let assistant = ChatCompletionRequestAssistantMessageArgs::default()
+            .tool_calls(vec![
+                tool_call("1", "echo", "{}"),
+                tool_call("2", "echo", "{}"),
+            ])
+            .build()
+            .unwrap();
+        let answer = ChatCompletionRequestToolMessageArgs::default()
+            .content("done")
+            .tool_call_id("1")
+            .build()
+            .unwrap();
+        let state = MessagesState::new(vec![
+            ChatCompletionRequestSystemMessage::from("Be brief").into(),
+            ChatCompletionRequestUserMessage::from("first").into(),
+            ChatCompletionRequestUserMessage::from("second").into(),
+            assistant.clone().into(),
+            answer.into(),
+        ]);
+
+        assert!(matches!(
+            state.last_message(),
+            Some(ChatCompletionRequestMessage::Tool(_))
+        ));
+        assert_eq!(state.last_assistant_message(), Some(&assistant));
+        let pending = state.pending_tool_calls();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].id, "2");
+        assert_eq!(tools_condition(&state, "run_tools"), "run_tools");
+        assert_eq!(state.messages_with_role(Role::User).len(), 2);
+
+        // System messages are kept and the tool reply is not cut from its call
+        let trimmed = state.trimmed_messages(3);
+        assert_eq!(trimmed.len(), 4);
+        assert_eq!(trimmed[0], state.messages[0]);
+        assert_eq!(trimmed[1], state.messages[2]);
+
+        // Even with room for one message the latest turn stays whole
+        let trimmed = state.trimmed_messages(1);
+        assert_eq!(
+            trimmed,
+            vec![
+                state.messages[0].clone(),
+                state.messages[3].clone(),
+                state.messages[4].clone(),
+            ]
+        );
+        let trimmed = crate::prebuilt::trim_messages(&state.messages[..4], 1);
+        assert_eq!(
+            trimmed,
+            vec![state.messages[0].clone(), state.messages[3].clone()]
+        );
+    }
+
+    #[derive(State, Debug, Clone, Default)]
+    struct IdChatState {
+        #[update(messages)]
+        #[state(messages)]
+        messages: Vec<Message>,
+    }
+
+    #[tokio::test]
+    async fn test_message_id_history() {
+        let client = Arc::new(ScriptedClient::default());
+        let agent: ChatNode<IdChatState> =
+            ChatNode::new(client.clone(), ChatCompletionRequestOptions::default());
+        let mut graph = Graph::new("id_chat");
+        graph
+            .add_node(agent)
+            .add_node(ToolNode::new(ToolRegistry::new().with(Echo)))
+            .add_edge(START, "agent")
+            .add_conditional_edge("agent", |state: &IdChatState| {
+                tools_condition(state, "tools")
+            })
+            .add_edge("tools", END);
+
+        let state = IdChatState {
+            messages: vec![Message::with_id(
+                "question",
+                ChatCompletionRequestUserMessage::from("go"),
+            )],
+        };
+        let state = graph
+            .build()
+            .run(&Context::new("test"), state)
+            .await
+            .unwrap();
+
+        // Replies are stored under fresh ids and the tool call is answered
+        assert_eq!(state.messages.len(), 3);
+        assert_eq!(state.messages[0].id, "question");
+        assert_ne!(state.messages[1].id, state.messages[2].id);
+        assert!(state.last_assistant_message().is_some());
+        assert!(!state.has_pending_tool_calls());
+        let messages: Vec<ChatCompletionRequestMessage> =
+            state.messages().into_iter().cloned().collect();
+        assert_eq!(
+            tool_results(&messages),
+            [("call_1".to_string(), "\"hi\"".to_string())]
+        );
+        assert_eq!(client.requests.lock().unwrap()[0].0.messages.len(), 1);
+    }
+}
@@ -0,0 +1,94 @@
+use super::HasMessages;
+use crate::node::{Context, Node};
+use crate::tool::ToolRegistry;
+use crate::types::{NodeOutput, NodeResult};
+use async_openai::types::{ChatCompletionRequestMessage, ChatCompletionRequestToolMessageArgs};
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::fmt::{Debug, Formatter, Result};
+use std::marker::PhantomData;
+
+/// Node that runs the pending tool calls of the latest assistant message
+/// through a `ToolRegistry` and appends one tool message per call id.
+///
+/// Unknown tools and invalid arguments are reported back to the model as
+/// error tool messages instead of failing the node.
+pub struct ToolNode<S> {
+    name: String,
+    tools: ToolRegistry,
+    concurrency: usize,
+    _state: PhantomData<fn() -> S>,
+}
+
+impl<S: HasMessages> ToolNode<S> {
+    /// A node named "tools" running up to 4 calls at once
+    pub fn new(tools: ToolRegistry) -> Self {
+        Self {
+            name: "tools".to_string(),
+            tools,
+            concurrency: 4,
+            _state: PhantomData,
+        }
+    }
+
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Maximum number of tool calls run at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub fn tools(&self) -> &ToolRegistry {
+        &self.tools
+    }
+}
+
+#[async_trait]
+impl<S: HasMessages> Node<S> for ToolNode<S> {
+    async fn process(&self, ctx: &Context, state: S) -> NodeResult<S> {
+        let results: Vec<(String, String)> = futures::stream::iter(state.pending_tool_calls())
+            .map(|call| async move {
+                let content = match self.tools.execute_with_context(ctx, &call).await {
+                    Ok(result) => result.to_string(),
+                    Err(e) => format!("Error: {}", e),
+                };
+                (call.id, content)
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await;
+        if results.is_empty() {
+            return Ok(NodeOutput::Updates(vec![]));
+        }
+
+        let messages = results
+            .into_iter()
+            .map(|(id, content)| {
+                ChatCompletionRequestToolMessageArgs::default()
+                    .content(content)
+                    .tool_call_id(id)
+                    .build()
+                    .map(ChatCompletionRequestMessage::Tool)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(NodeOutput::Updates(vec![S::append_messages(messages)]))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<S> Debug for ToolNode<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ToolNode")
+            .field("name", &self.name)
+            .field("tools", &self.tools)
+            .field("concurrency", &self.concurrency)
+            .finish()
+    }
+}
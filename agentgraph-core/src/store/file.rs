@@ -0,0 +1,182 @@
+use super::{check_key, owned, Item, Store};
+use crate::types::StoreError;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+const ITEM_EXTENSION: &str = "json";
+
+/// Store persisted on disk under a root directory.
+///
+/// Each namespace part becomes a directory and each item a JSON file named
+/// after its key. Characters other than ASCII letters, digits, `-` and `_`
+/// are percent-encoded in file names. Writes to one key are serialized
+/// across clones of the store, but not across processes.
+#[derive(Debug, Clone)]
+pub struct FileStore {
+    root: PathBuf,
+    // Per-item write locks, dropped once no writer holds or waits on them
+    locks: Arc<Mutex<HashMap<PathBuf, Arc<tokio::sync::Mutex<()>>>>>,
+}
+
+impl FileStore {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            locks: Arc::default(),
+        }
+    }
+
+    fn namespace_dir(&self, namespace: &[&str]) -> PathBuf {
+        namespace
+            .iter()
+            .fold(self.root.clone(), |dir, part| dir.join(encode(part)))
+    }
+
+    async fn write_item(
+        &self,
+        namespace: &[&str],
+        key: &str,
+        value: Value,
+        path: &Path,
+    ) -> Result<(), StoreError> {
+        let now = Utc::now();
+        let created_at = self
+            .get(namespace, key)
+            .await?
+            .map_or(now, |item| item.created_at);
+        let item = Item {
+            namespace: owned(namespace),
+            key: key.to_string(),
+            value,
+            created_at,
+            updated_at: now,
+        };
+
+        let dir = self.namespace_dir(namespace);
+        tokio::fs::create_dir_all(&dir).await?;
+        // Write to a uniquely named temporary file first so readers never see
+        // a partial item. Encoded keys never start with `.`, so it cannot
+        // clash with an item file.
+        let tmp = dir.join(format!(".{}.{}.tmp", encode(key), uuid::Uuid::new_v4()));
+        if let Err(e) = tokio::fs::write(&tmp, serde_json::to_vec_pretty(&item)?).await {
+            let _ = tokio::fs::remove_file(&tmp).await;
+            return Err(e.into());
+        }
+        tokio::fs::rename(&tmp, path).await?;
+        Ok(())
+    }
+
+    fn item_path(&self, namespace: &[&str], key: &str) -> Result<PathBuf, StoreError> {
+        check_key(key)?;
+        Ok(self
+            .namespace_dir(namespace)
+            .join(format!("{}.{}", encode(key), ITEM_EXTENSION)))
+    }
+}
+
+/// Read every item file directly inside `dir`, returning the sub-directories
+/// found alongside them
+async fn read_dir(dir: &Path) -> Result<(Vec<Item>, Vec<PathBuf>), StoreError> {
+    let mut items = vec![];
+    let mut dirs = vec![];
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok((items, dirs)),
+        Err(e) => return Err(e.into()),
+    };
+    while let Some(entry) = entries.next_entry().await? {
+        let path = entry.path();
+        if entry.file_type().await?.is_dir() {
+            dirs.push(path);
+        } else if path.extension().is_some_and(|ext| ext == ITEM_EXTENSION) {
+            let bytes = tokio::fs::read(&path).await?;
+            items.push(serde_json::from_slice(&bytes)?);
+        }
+    }
+    Ok((items, dirs))
+}
+
+fn sort(items: &mut [Item]) {
+    items.sort_by(|a, b| (&a.namespace, &a.key).cmp(&(&b.namespace, &b.key)));
+}
+
+#[async_trait]
+impl Store for FileStore {
+    async fn put(&self, namespace: &[&str], key: &str, value: Value) -> Result<(), StoreError> {
+        let path = self.item_path(namespace, key)?;
+        let lock = self
+            .locks
+            .lock()
+            .unwrap()
+            .entry(path.clone())
+            .or_default()
+            .clone();
+
+        // Hold the item's lock so `created_at` is carried over by one writer at a time
+        let result = {
+            let _guard = lock.lock().await;
+            self.write_item(namespace, key, value, &path).await
+        };
+
+        let mut locks = self.locks.lock().unwrap();
+        // Only the map and this call hold the lock, so nobody else is waiting
+        if Arc::strong_count(&lock) == 2 {
+            locks.remove(&path);
+        }
+        result
+    }
+
+    async fn get(&self, namespace: &[&str], key: &str) -> Result<Option<Item>, StoreError> {
+        match tokio::fs::read(self.item_path(namespace, key)?).await {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete(&self, namespace: &[&str], key: &str) -> Result<(), StoreError> {
+        match tokio::fs::remove_file(self.item_path(namespace, key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    async fn list(&self, namespace: &[&str]) -> Result<Vec<Item>, StoreError> {
+        let (mut items, _) = read_dir(&self.namespace_dir(namespace)).await?;
+        sort(&mut items);
+        Ok(items)
+    }
+
+    async fn search(&self, namespace_prefix: &[&str]) -> Result<Vec<Item>, StoreError> {
+        let mut found = vec![];
+        let mut pending = vec![self.namespace_dir(namespace_prefix)];
+        while let Some(dir) = pending.pop() {
+            let (items, dirs) = read_dir(&dir).await?;
+            found.extend(items);
+            pending.extend(dirs);
+        }
+        sort(&mut found);
+        Ok(found)
+    }
+}
+
+fn encode(part: &str) -> String {
+    let mut encoded = String::with_capacity(part.len());
+    for byte in part.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    if encoded.is_empty() {
+        // Keep empty namespace parts distinct from their parent directory
+        encoded.push('%');
+    }
+    encoded
+}
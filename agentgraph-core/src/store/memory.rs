@@ -0,0 +1,74 @@
+use super::{check_key, owned, Item, Store};
+use crate::types::StoreError;
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+/// Store kept in process memory; contents are lost when it is dropped
+#[derive(Debug, Default)]
+pub struct InMemoryStore {
+    items: RwLock<BTreeMap<(Vec<String>, String), Item>>,
+}
+
+impl InMemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Store for InMemoryStore {
+    async fn put(&self, namespace: &[&str], key: &str, value: Value) -> Result<(), StoreError> {
+        check_key(key)?;
+        let now = Utc::now();
+        let mut items = self.items.write().unwrap();
+        items
+            .entry((owned(namespace), key.to_string()))
+            .and_modify(|item| {
+                item.value = value.clone();
+                item.updated_at = now;
+            })
+            .or_insert_with(|| Item {
+                namespace: owned(namespace),
+                key: key.to_string(),
+                value,
+                created_at: now,
+                updated_at: now,
+            });
+        Ok(())
+    }
+
+    async fn get(&self, namespace: &[&str], key: &str) -> Result<Option<Item>, StoreError> {
+        check_key(key)?;
+        let items = self.items.read().unwrap();
+        Ok(items.get(&(owned(namespace), key.to_string())).cloned())
+    }
+
+    async fn delete(&self, namespace: &[&str], key: &str) -> Result<(), StoreError> {
+        check_key(key)?;
+        let mut items = self.items.write().unwrap();
+        items.remove(&(owned(namespace), key.to_string()));
+        Ok(())
+    }
+
+    async fn list(&self, namespace: &[&str]) -> Result<Vec<Item>, StoreError> {
+        let items = self.items.read().unwrap();
+        Ok(items
+            .values()
+            .filter(|item| item.namespace == namespace)
+            .cloned()
+            .collect())
+    }
+
+    async fn search(&self, namespace_prefix: &[&str]) -> Result<Vec<Item>, StoreError> {
+        let prefix = owned(namespace_prefix);
+        let items = self.items.read().unwrap();
+        Ok(items
+            .values()
+            .filter(|item| item.namespace.starts_with(&prefix))
+            .cloned()
+            .collect())
+    }
+}
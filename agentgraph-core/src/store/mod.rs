@@ -0,0 +1,63 @@
+//! Long-term memory shared across graph runs and threads.
+//!
+//! Items are JSON values filed under a namespace (a path such as
+//! `["users", "42", "preferences"]`) and a key. Nodes reach the store through
+//! `Context::store`.
+
+#[cfg(feature = "persistence")]
+mod file;
+mod memory;
+mod tests;
+
+use crate::types::StoreError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt::Debug;
+
+#[cfg(feature = "persistence")]
+pub use file::FileStore;
+pub use memory::InMemoryStore;
+
+/// A stored value with its location and timestamps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub namespace: Vec<String>,
+    pub key: String,
+    pub value: Value,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Namespaced key/value storage shared by nodes
+#[async_trait]
+pub trait Store: Send + Sync + Debug {
+    /// Insert or overwrite the value at `namespace` / `key`
+    async fn put(&self, namespace: &[&str], key: &str, value: Value) -> Result<(), StoreError>;
+
+    /// Fetch the item at `namespace` / `key`
+    async fn get(&self, namespace: &[&str], key: &str) -> Result<Option<Item>, StoreError>;
+
+    /// Remove the item at `namespace` / `key`, if any
+    async fn delete(&self, namespace: &[&str], key: &str) -> Result<(), StoreError>;
+
+    /// Items directly in `namespace`, sorted by key
+    async fn list(&self, namespace: &[&str]) -> Result<Vec<Item>, StoreError>;
+
+    /// Items in `namespace_prefix` or any namespace nested below it, sorted by
+    /// namespace and key
+    async fn search(&self, namespace_prefix: &[&str]) -> Result<Vec<Item>, StoreError>;
+}
+
+/// Key rules shared by every store, so items move between them unchanged
+fn check_key(key: &str) -> Result<(), StoreError> {
+    if key.is_empty() {
+        return Err(StoreError::InvalidKey("key must not be empty".into()));
+    }
+    Ok(())
+}
+
+fn owned(namespace: &[&str]) -> Vec<String> {
+    namespace.iter().map(|part| part.to_string()).collect()
+}
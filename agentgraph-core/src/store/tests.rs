@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod tests {
+    use crate::store::*;
+    use agentgraph_core::*;
+    use agentgraph_macros::State;
+    use serde_json::json;
+    use std::sync::Arc;
+
+    async fn exercise(store: &dyn Store) {
+        store
+            .put(&["users", "ryan"], "theme", json!("dark"))
+            .await
+            .unwrap();
+        store
+            .put(&["users", "ryan"], "language", json!("en"))
+            .await
+            .unwrap();
+        store
+            .put(
+                &["users", "ryan", "projects"],
+                "agentgraph",
+                json!({ "stars": 1 }),
+            )
+            .await
+            .unwrap();
+        store
+            .put(&["users", "sam"], "theme", json!("light"))
+            .await
+            .unwrap();
+
+        let item = store
+            .get(&["users", "ryan"], "theme")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(item.value, json!("dark"));
+        assert_eq!(item.namespace, vec!["users", "ryan"]);
+
+        // Overwriting keeps the creation time
+        store
+            .put(&["users", "ryan"], "theme", json!("light"))
+            .await
+            .unwrap();
+        let updated = store
+            .get(&["users", "ryan"], "theme")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.value, json!("light"));
+        assert_eq!(updated.created_at, item.created_at);
+
+        let keys: Vec<String> = store
+            .list(&["users", "ryan"])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| item.key)
+            .collect();
+        assert_eq!(keys, vec!["language", "theme"]);
+
+        let found: Vec<(Vec<String>, String)> = store
+            .search(&["users"])
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|item| (item.namespace, item.key))
+            .collect();
+        assert_eq!(found.len(), 4);
+        assert_eq!(found[2].1, "agentgraph");
+        assert_eq!(found[3].0, vec!["users", "sam"]);
+
+        store.delete(&["users", "ryan"], "theme").await.unwrap();
+        store.delete(&["users", "ryan"], "missing").await.unwrap();
+        assert!(store
+            .get(&["users", "ryan"], "theme")
+            .await
+            .unwrap()
+            .is_none());
+
+        // Every store rejects the same keys
+        let invalid =
+            |result: Result<_, StoreError>| matches!(result, Err(StoreError::InvalidKey(_)));
+        assert!(invalid(store.put(&["users"], "", json!(1)).await));
+        assert!(invalid(store.get(&["users"], "").await.map(|_| ())));
+        assert!(invalid(store.delete(&["users"], "").await));
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store() {
+        exercise(&InMemoryStore::new()).await;
+    }
+
+    #[cfg(feature = "persistence")]
+    #[tokio::test]
+    async fn test_file_store() {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        exercise(&FileStore::new(&root)).await;
+
+        // Keys are encoded into safe file names
+        let store = FileStore::new(&root);
+        store.put(&["a/b"], "../key", json!(1)).await.unwrap();
+        let item = store.get(&["a/b"], "../key").await.unwrap().unwrap();
+        assert_eq!(item.key, "../key");
+        assert!(root.join("a%2Fb").join("%2E%2E%2Fkey.json").exists());
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[cfg(feature = "persistence")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_file_store_concurrent_puts() {
+        let root = std::env::temp_dir().join(uuid::Uuid::new_v4().to_string());
+        let store = FileStore::new(&root);
+        store.put(&["counters"], "hits", json!(0)).await.unwrap();
+        let created_at = store
+            .get(&["counters"], "hits")
+            .await
+            .unwrap()
+            .unwrap()
+            .created_at;
+
+        let puts = (1..=32).map(|n| {
+            let store = store.clone();
+            tokio::spawn(async move { store.put(&["counters"], "hits", json!(n)).await })
+        });
+        for result in futures::future::join_all(puts).await {
+            result.unwrap().unwrap();
+        }
+
+        let items = store.list(&["counters"]).await.unwrap();
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].created_at, created_at);
+        // No temporary files are left behind
+        let files = std::fs::read_dir(root.join("counters")).unwrap().count();
+        assert_eq!(files, 1);
+
+        std::fs::remove_dir_all(root).unwrap();
+    }
+
+    #[derive(State, Debug, Clone)]
+    struct VisitState {
+        #[update(replace)]
+        visits: i64,
+    }
+
+    #[tokio::test]
+    async fn test_store_shared_across_runs() {
+        let node = FunctionNode::new("count", |ctx: &Context, _state: VisitState| {
+            let store = ctx.store().cloned();
+            async move {
+                let store = store.ok_or_else(|| NodeError::Execution("no store".into()))?;
+                let visits = store
+                    .get(&["visits"], "count")
+                    .await?
+                    .and_then(|item| item.value.as_i64())
+                    .unwrap_or(0)
+                    + 1;
+                store.put(&["visits"], "count", json!(visits)).await?;
+                Ok(NodeOutput::Updates(vec![VisitStateUpdate::Visits(visits)]))
+            }
+        });
+
+        let built_graph = {
+            let mut graph = Graph::new("g");
+            graph
+                .add_node(node)
+                .add_edge(START, "count")
+                .add_edge("count", END);
+            graph.build()
+        };
+
+        let store: Arc<dyn Store> = Arc::new(InMemoryStore::new());
+        for expected in 1..=2 {
+            let ctx = Context::default().with_store(store.clone());
+            let result = built_graph
+                .run(&ctx, VisitState { visits: 0 })
+                .await
+                .unwrap();
+            assert_eq!(result.visits, expected);
+        }
+    }
+}
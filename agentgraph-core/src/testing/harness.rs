@@ -0,0 +1,238 @@
+use super::nodes::{ScriptedNode, StubNode};
+use crate::graph::{Built, Graph, NotBuilt};
+use crate::node::{Context, Node};
+use crate::types::{GraphResult, GraphState, NodeResult};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::{Arc, Mutex};
+
+/// One attempt at running a node, as recorded by the harness
+#[derive(Debug, Clone)]
+pub struct Step<S> {
+    /// Name of the node
+    pub node: String,
+    /// State the node was given
+    pub input: S,
+    /// Whether the node returned successfully. False if it failed, timed
+    /// out or was still running when the graph stopped.
+    pub succeeded: bool,
+}
+
+/// Outcome of a harness run: the graph result plus every recorded step
+#[derive(Debug)]
+pub struct Trace<S> {
+    pub result: GraphResult<S>,
+    pub steps: Vec<Step<S>>,
+}
+
+impl<S> Trace<S> {
+    /// Names of the nodes that completed, in execution order
+    pub fn path(&self) -> Vec<&str> {
+        self.completed().map(|step| step.node.as_str()).collect()
+    }
+
+    /// Number of times a node completed
+    pub fn visits(&self, node: &str) -> usize {
+        self.completed().filter(|step| step.node == node).count()
+    }
+
+    /// Number of times a node was attempted, including failed retries
+    pub fn attempts(&self, node: &str) -> usize {
+        self.steps.iter().filter(|step| step.node == node).count()
+    }
+
+    /// The initial state followed by the state after each completed node.
+    /// The last entry is only present if the run succeeded.
+    pub fn states(&self) -> Vec<&S> {
+        let mut states: Vec<&S> = self.completed().map(|step| &step.input).collect();
+        if let Ok(state) = &self.result {
+            states.push(state);
+        }
+        states
+    }
+
+    /// States a node was given on each completed visit
+    pub fn inputs_of(&self, node: &str) -> Vec<&S> {
+        self.completed()
+            .filter(|step| step.node == node)
+            .map(|step| &step.input)
+            .collect()
+    }
+
+    /// Final state of the run, panicking if the graph failed
+    pub fn final_state(&self) -> &S {
+        match &self.result {
+            Ok(state) => state,
+            Err(e) => panic!("Graph run failed: {}", e),
+        }
+    }
+
+    /// Assert the exact sequence of completed nodes
+    pub fn assert_path(&self, expected: &[&str]) {
+        assert_eq!(self.path(), expected, "unexpected node path");
+    }
+
+    /// Assert how many times a node completed
+    pub fn assert_visits(&self, node: &str, expected: usize) {
+        assert_eq!(
+            self.visits(node),
+            expected,
+            "unexpected visit count for node {}",
+            node
+        );
+    }
+
+    fn completed(&self) -> impl Iterator<Item = &Step<S>> {
+        self.steps.iter().filter(|step| step.succeeded)
+    }
+}
+
+/// Steps of one harness run, handed to the recording nodes as a context
+/// extension so that concurrent runs keep separate logs
+struct StepLog<S>(Mutex<Vec<Step<S>>>);
+
+/// Wraps a node and records every attempt into the run's step log
+struct RecordingNode<S: GraphState> {
+    inner: Arc<dyn Node<S>>,
+}
+
+#[async_trait]
+impl<S: GraphState> Node<S> for RecordingNode<S> {
+    async fn process(&self, ctx: &Context, state: S) -> NodeResult<S> {
+        // Runs started outside the harness are not recorded
+        let Some(log) = ctx.extension_arc::<StepLog<S>>() else {
+            return self.inner.process(ctx, state).await;
+        };
+
+        // Record the attempt up front, as a timed out node never returns
+        let index = {
+            let mut steps = log.0.lock().unwrap();
+            steps.push(Step {
+                node: self.inner.name().to_string(),
+                input: state.clone(),
+                succeeded: false,
+            });
+            steps.len() - 1
+        };
+        let result = self.inner.process(ctx, state).await;
+        log.0.lock().unwrap()[index].succeeded = result.is_ok();
+        result
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn subgraph(&self) -> Option<&Graph<S, Built>> {
+        self.inner.subgraph()
+    }
+}
+
+impl<S: GraphState> Debug for RecordingNode<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        self.debug_node(f)
+    }
+}
+
+/// Builder that swaps named nodes of a graph for scripted outputs or stubs
+pub struct GraphHarnessBuilder<S: GraphState> {
+    graph: Graph<S, NotBuilt>,
+    replacements: HashMap<String, Arc<dyn Node<S>>>,
+}
+
+impl<S> GraphHarnessBuilder<S>
+where
+    S: GraphState,
+    S::Update: Send,
+{
+    pub fn new(graph: Graph<S, NotBuilt>) -> Self {
+        Self {
+            graph,
+            replacements: HashMap::new(),
+        }
+    }
+
+    /// Replace a node with one returning `outputs` in order, one per attempt
+    pub fn script(
+        self,
+        name: impl Into<String>,
+        outputs: impl IntoIterator<Item = NodeResult<S>>,
+    ) -> Self {
+        let name = name.into();
+        let node = ScriptedNode::new(name.clone(), outputs);
+        self.replace(name, node)
+    }
+
+    /// Replace a node with a synchronous stub over the current state
+    pub fn stub<F>(self, name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(&Context, &S) -> NodeResult<S> + Send + Sync + 'static,
+    {
+        let name = name.into();
+        let node = StubNode::new(name.clone(), f);
+        self.replace(name, node)
+    }
+
+    /// Replace a node with any other node
+    pub fn replace<N>(mut self, name: impl Into<String>, node: N) -> Self
+    where
+        N: Node<S> + 'static,
+    {
+        self.replacements.insert(name.into(), Arc::new(node));
+        self
+    }
+
+    /// Build the harness.
+    ///
+    /// Panics if a replaced node does not exist in the graph.
+    pub fn build(self) -> GraphHarness<S> {
+        let mut graph = self.graph;
+        for (name, node) in self.replacements {
+            let Some(slot) = graph.nodes.get_mut(&name) else {
+                panic!("Cannot replace unknown node: {}", name);
+            };
+            *slot = node;
+        }
+
+        for node in graph.nodes.values_mut() {
+            *node = Arc::new(RecordingNode {
+                inner: node.clone(),
+            });
+        }
+
+        GraphHarness {
+            graph: graph.build(),
+        }
+    }
+}
+
+/// A built graph whose node executions are recorded for assertions
+pub struct GraphHarness<S: GraphState> {
+    graph: Graph<S, Built>,
+}
+
+impl<S> GraphHarness<S>
+where
+    S: GraphState,
+    S::Update: Send,
+{
+    pub fn builder(graph: Graph<S, NotBuilt>) -> GraphHarnessBuilder<S> {
+        GraphHarnessBuilder::new(graph)
+    }
+
+    /// The graph under test
+    pub fn graph(&self) -> &Graph<S, Built> {
+        &self.graph
+    }
+
+    /// Run the graph and return its result with the recorded steps. Runs
+    /// may overlap; each trace only holds its own steps.
+    pub async fn run(&self, ctx: &Context, initial_state: S) -> Trace<S> {
+        let log = Arc::new(StepLog(Mutex::new(vec![])));
+        let ctx = ctx.clone().with_extension_arc(log.clone());
+        let result = self.graph.run(&ctx, initial_state).await;
+        let steps = std::mem::take(&mut *log.0.lock().unwrap());
+        Trace { result, steps }
+    }
+}
@@ -0,0 +1,9 @@
+//! Utilities for testing graphs: replace nodes with scripted outputs or stubs,
+//! run the graph and assert on the path it took.
+
+mod harness;
+mod nodes;
+mod tests;
+
+pub use harness::{GraphHarness, GraphHarnessBuilder, Step, Trace};
+pub use nodes::{ScriptedNode, StubNode};
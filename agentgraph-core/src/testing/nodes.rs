@@ -0,0 +1,92 @@
+use crate::node::{Context, Node};
+use crate::types::{GraphState, NodeError, NodeResult};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::Mutex;
+
+/// A node that returns pre-scripted results, one per call, in order
+pub struct ScriptedNode<S: GraphState> {
+    name: String,
+    outputs: Mutex<VecDeque<NodeResult<S>>>,
+}
+
+impl<S: GraphState> ScriptedNode<S> {
+    pub fn new(name: impl Into<String>, outputs: impl IntoIterator<Item = NodeResult<S>>) -> Self {
+        Self {
+            name: name.into(),
+            outputs: Mutex::new(outputs.into_iter().collect()),
+        }
+    }
+
+    /// Number of scripted results not yet returned
+    pub fn remaining(&self) -> usize {
+        self.outputs.lock().unwrap().len()
+    }
+}
+
+#[async_trait]
+impl<S: GraphState> Node<S> for ScriptedNode<S>
+where
+    S::Update: Send,
+{
+    async fn process(&self, _ctx: &Context, _state: S) -> NodeResult<S> {
+        self.outputs.lock().unwrap().pop_front().unwrap_or_else(|| {
+            Err(NodeError::Execution(format!(
+                "Scripted node {} has no outputs left",
+                self.name
+            )))
+        })
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<S: GraphState> Debug for ScriptedNode<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ScriptedNode")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+type StubFn<S> = Box<dyn Fn(&Context, &S) -> NodeResult<S> + Send + Sync>;
+
+/// A node backed by a synchronous closure over the current state
+pub struct StubNode<S: GraphState> {
+    name: String,
+    f: StubFn<S>,
+}
+
+impl<S: GraphState> StubNode<S> {
+    pub fn new<F>(name: impl Into<String>, f: F) -> Self
+    where
+        F: Fn(&Context, &S) -> NodeResult<S> + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            f: Box::new(f),
+        }
+    }
+}
+
+#[async_trait]
+impl<S: GraphState> Node<S> for StubNode<S> {
+    async fn process(&self, ctx: &Context, state: S) -> NodeResult<S> {
+        (self.f)(ctx, &state)
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl<S: GraphState> Debug for StubNode<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("StubNode")
+            .field("name", &self.name)
+            .finish()
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    use crate::testing::*;
+    use agentgraph_core::*;
+    use agentgraph_macros::State;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    #[derive(State, Debug, Clone, PartialEq)]
+    struct CounterState {
+        #[update(replace)]
+        count: i32,
+    }
+
+    fn counter_graph() -> Graph<CounterState> {
+        let increment = FunctionNode::new("increment", |_ctx, state: CounterState| async move {
+            Ok(NodeOutput::Updates(vec![CounterStateUpdate::Count(
+                state.count + 1,
+            )]))
+        });
+        let check = FunctionNode::new("check", |_ctx, _state: CounterState| async move {
+            Ok(NodeOutput::Updates(vec![]))
+        });
+
+        let mut graph = Graph::new("g");
+        graph
+            .add_node(increment)
+            .add_node(check)
+            .add_edge(START, "increment")
+            .add_edge("increment", "check")
+            .add_conditional_edge("check", |state: &CounterState| {
+                if state.count < 3 {
+                    "increment".into()
+                } else {
+                    END.into()
+                }
+            });
+        graph
+    }
+
+    #[tokio::test]
+    async fn test_records_path_and_states() {
+        let harness = GraphHarness::builder(counter_graph()).build();
+
+        let ctx = Context::new("test");
+        let trace = harness.run(&ctx, CounterState { count: 1 }).await;
+
+        trace.assert_path(&["increment", "check", "increment", "check"]);
+        trace.assert_visits("increment", 2);
+        assert_eq!(trace.final_state().count, 3);
+        let counts: Vec<i32> = trace.states().iter().map(|s| s.count).collect();
+        assert_eq!(counts, vec![1, 2, 2, 3, 3]);
+        assert_eq!(trace.inputs_of("check")[0].count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scripted_and_stubbed_nodes() {
+        let harness = GraphHarness::builder(counter_graph())
+            .script(
+                "increment",
+                vec![
+                    Err(NodeError::Execution("flaky".into())),
+                    Ok(NodeOutput::Updates(vec![CounterStateUpdate::Count(10)])),
+                ],
+            )
+            .stub("check", |_ctx, state| {
+                Ok(NodeOutput::goto(
+                    vec![CounterStateUpdate::Count(state.count * 2)],
+                    END,
+                ))
+            })
+            .build();
+
+        let ctx = Context::new("test");
+        let trace = harness.run(&ctx, CounterState { count: 0 }).await;
+
+        trace.assert_path(&["increment", "check"]);
+        assert_eq!(trace.attempts("increment"), 2);
+        assert_eq!(trace.final_state().count, 20);
+
+        // The script is exhausted, so the next run fails after retries
+        let trace = harness.run(&ctx, CounterState { count: 0 }).await;
+        assert!(trace.result.is_err());
+        trace.assert_path(&[]);
+        assert_eq!(trace.attempts("increment"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_timed_out_attempts() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let slow_once = {
+            let calls = calls.clone();
+            FunctionNode::new("increment", move |_ctx, state: CounterState| {
+                let calls = calls.clone();
+                async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                    }
+                    Ok(NodeOutput::Updates(vec![CounterStateUpdate::Count(
+                        state.count + 1,
+                    )]))
+                }
+            })
+        };
+        let mut graph = counter_graph();
+        graph.configure_node(
+            "increment",
+            node::NodeConfig {
+                timeout: 1,
+                ..Default::default()
+            },
+        );
+        let harness = GraphHarness::builder(graph)
+            .replace("increment", slow_once)
+            .build();
+
+        let ctx = Context::new("test");
+        let trace = harness.run(&ctx, CounterState { count: 2 }).await;
+
+        trace.assert_path(&["increment", "check"]);
+        assert_eq!(trace.attempts("increment"), 2);
+        assert!(!trace.steps[0].succeeded);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_runs() {
+        let harness = GraphHarness::builder(counter_graph())
+            .stub("increment", |_ctx, state| {
+                // Keep the runs overlapping
+                std::thread::sleep(Duration::from_millis(10));
+                Ok(NodeOutput::Updates(vec![CounterStateUpdate::Count(
+                    state.count + 1,
+                )]))
+            })
+            .build();
+
+        let ctx = Context::new("test");
+        let (short, long) = tokio::join!(
+            harness.run(&ctx, CounterState { count: 2 }),
+            harness.run(&ctx, CounterState { count: -2 }),
+        );
+
+        short.assert_path(&["increment", "check"]);
+        long.assert_visits("increment", 5);
+        assert_eq!(long.steps.len(), 10);
+    }
+}
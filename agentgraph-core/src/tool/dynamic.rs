@@ -0,0 +1,72 @@
+use super::{validate_arguments, ChatCompletionTool, ToolFunction};
+use crate::node::Context;
+use crate::types::ToolError;
+use async_trait::async_trait;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Object-safe view of a tool that takes and returns raw JSON, so tools of
+/// different types can be stored together. Implemented for every
+/// `ToolFunction`.
+#[async_trait]
+pub trait DynTool: Send + Sync {
+    fn tool_name(&self) -> &str;
+    fn tool_description(&self) -> &str;
+
+    /// Schema to pass to the model in `ChatCompletionRequestOptions::tools`
+    fn tool_schema(&self) -> ChatCompletionTool;
+
+    /// Validate and deserialize `arguments`, run the tool and serialize its
+    /// response
+    async fn call(&self, arguments: Value) -> Result<Value, ToolError>;
+
+    /// Like `call`, for a call made from within a graph run
+    async fn call_with_context(
+        &self,
+        _ctx: &Context,
+        arguments: Value,
+    ) -> Result<Value, ToolError> {
+        self.call(arguments).await
+    }
+}
+
+#[async_trait]
+impl<T> DynTool for T
+where
+    T: ToolFunction + Send + Sync,
+    T::Params: Send,
+{
+    fn tool_name(&self) -> &str {
+        T::name()
+    }
+
+    fn tool_description(&self) -> &str {
+        T::description()
+    }
+
+    fn tool_schema(&self) -> ChatCompletionTool {
+        T::get_schema()
+    }
+
+    async fn call(&self, arguments: Value) -> Result<Value, ToolError> {
+        let response = self.execute(params::<T>(arguments)?).await?;
+        to_value(response)
+    }
+
+    async fn call_with_context(&self, ctx: &Context, arguments: Value) -> Result<Value, ToolError> {
+        let response = self
+            .execute_with_context(ctx, params::<T>(arguments)?)
+            .await?;
+        to_value(response)
+    }
+}
+
+/// Validate and deserialize the arguments of a call to `T`
+fn params<T: ToolFunction>(arguments: Value) -> Result<T::Params, ToolError> {
+    validate_arguments(&T::parameters_schema(), &arguments)?;
+    serde_json::from_value(arguments).map_err(|e| ToolError::Schema(e.to_string()))
+}
+
+fn to_value(response: impl Serialize) -> Result<Value, ToolError> {
+    serde_json::to_value(response).map_err(|e| ToolError::Serialization(e.to_string()))
+}
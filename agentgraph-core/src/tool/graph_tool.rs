@@ -0,0 +1,103 @@
+use super::{JsonSchema, ToolFunction};
+use crate::graph::{Built, Graph};
+use crate::node::Context;
+use crate::types::{GraphState, ToolError};
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+/// Describes how a built graph is exposed as a tool: its name and description,
+/// how tool parameters become the initial state, and how the final state is
+/// projected into the tool response.
+pub trait GraphToolSpec: Send + Sync + 'static {
+    /// State of the wrapped graph
+    type State: GraphState;
+
+    /// The parameter type for the tool
+    type Params: JsonSchema + DeserializeOwned + Send;
+
+    /// The response type for the tool
+    type Response: Serialize + Send;
+
+    fn name() -> &'static str;
+    fn description() -> &'static str;
+
+    /// Build the graph's initial state from the tool parameters
+    fn initial_state(params: Self::Params) -> Self::State;
+
+    /// Project the graph's final state into the tool response
+    fn response(state: Self::State) -> Self::Response;
+}
+
+/// Adapter that runs a built graph wherever a `ToolFunction` is expected
+pub struct GraphTool<T: GraphToolSpec> {
+    graph: Arc<Graph<T::State, Built>>,
+    ctx: Context,
+    _spec: PhantomData<T>,
+}
+
+impl<T: GraphToolSpec> GraphTool<T> {
+    pub fn new(graph: impl Into<Arc<Graph<T::State, Built>>>) -> Self {
+        Self {
+            graph: graph.into(),
+            ctx: Context::default(),
+            _spec: PhantomData,
+        }
+    }
+
+    /// Parent context for runs through `execute`, which has no caller's
+    /// context. Each run gets its own child context.
+    pub fn with_context(mut self, ctx: Context) -> Self {
+        self.ctx = ctx;
+        self
+    }
+
+    async fn run(&self, ctx: &Context, params: T::Params) -> Result<T::Response, ToolError> {
+        let ctx = ctx.next_node_context();
+        let state = self
+            .graph
+            .run(&ctx, T::initial_state(params))
+            .await
+            .map_err(|e| ToolError::Execution(e.to_string()))?;
+        Ok(T::response(state))
+    }
+}
+
+impl<T: GraphToolSpec> Clone for GraphTool<T> {
+    fn clone(&self) -> Self {
+        Self {
+            graph: self.graph.clone(),
+            ctx: self.ctx.clone(),
+            _spec: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: GraphToolSpec> ToolFunction for GraphTool<T> {
+    type Params = T::Params;
+    type Response = T::Response;
+
+    fn name() -> &'static str {
+        T::name()
+    }
+
+    fn description() -> &'static str {
+        T::description()
+    }
+
+    async fn execute(&self, params: Self::Params) -> Result<Self::Response, ToolError> {
+        self.run(&self.ctx, params).await
+    }
+
+    /// Runs the graph in a child of the caller's context, so it shares the
+    /// caller's store, extensions and run configuration
+    async fn execute_with_context(
+        &self,
+        ctx: &Context,
+        params: Self::Params,
+    ) -> Result<Self::Response, ToolError> {
+        self.run(ctx, params).await
+    }
+}
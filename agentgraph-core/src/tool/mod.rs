@@ -1,4 +1,10 @@
+mod dynamic;
+mod graph_tool;
+mod registry;
+mod validate;
+
 use super::types::ToolError;
+use crate::node::Context;
 use async_trait::async_trait;
 use schemars as sm; // rename for convenience
 use serde::{de::DeserializeOwned, Serialize};
@@ -7,6 +13,10 @@ use sm::JsonSchema as SchemarsJsonSchema;
 
 // Re-export key types and traits
 pub use async_openai::types::{ChatCompletionTool, ChatCompletionToolType, FunctionObject};
+pub use dynamic::DynTool;
+pub use graph_tool::{GraphTool, GraphToolSpec};
+pub use registry::ToolRegistry;
+pub use validate::{schema_violations, validate_arguments};
 
 /// Our existing trait
 pub trait JsonSchema {
@@ -24,10 +34,19 @@ where
     fn schema() -> Value {
         // schemars::schema_for!(T) returns a `Schema`.
         // We convert it to JSON via `serde_json::to_value`.
-        let schema_obj = sm::schema_for!(T).schema;
-        serde_json::to_value(&schema_obj).unwrap_or_else(
+        let root = sm::schema_for!(T);
+        let mut schema = serde_json::to_value(&root.schema).unwrap_or_else(
             |_| serde_json::json!({ "type": "object", "description": "error generating schema" }),
-        )
+        );
+        // Keep the definitions nested types `$ref` to
+        if !root.definitions.is_empty() {
+            if let (Value::Object(schema), Ok(definitions)) =
+                (&mut schema, serde_json::to_value(&root.definitions))
+            {
+                schema.insert("definitions".to_string(), definitions);
+            }
+        }
+        schema
     }
 }
 
@@ -64,4 +83,18 @@ pub trait ToolFunction {
     }
 
     async fn execute(&self, params: Self::Params) -> Result<Self::Response, ToolError>;
+
+    /// Run the tool for a call made from within a graph run. Tools that run
+    /// graphs themselves override this to run in the caller's context.
+    async fn execute_with_context(
+        &self,
+        _ctx: &Context,
+        params: Self::Params,
+    ) -> Result<Self::Response, ToolError>
+    where
+        Self: Sync,
+        Self::Params: Send,
+    {
+        self.execute(params).await
+    }
 }
@@ -0,0 +1,105 @@
+use super::{ChatCompletionTool, DynTool};
+use crate::node::Context;
+use crate::types::ToolError;
+use async_openai::types::ChatCompletionMessageToolCall;
+use serde_json::Value;
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::Arc;
+
+/// A set of tools, looked up by name, that collects their schemas for the
+/// model and dispatches the tool calls it makes
+#[derive(Clone, Default)]
+pub struct ToolRegistry {
+    tools: Vec<Arc<dyn DynTool>>,
+}
+
+impl ToolRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a tool, replacing any tool with the same name
+    pub fn with(mut self, tool: impl DynTool + 'static) -> Self {
+        self.register(Arc::new(tool));
+        self
+    }
+
+    /// Add a shared tool, replacing any tool with the same name
+    pub fn register(&mut self, tool: Arc<dyn DynTool>) -> &mut Self {
+        match self.position(tool.tool_name()) {
+            Some(index) => self.tools[index] = tool,
+            None => self.tools.push(tool),
+        }
+        self
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Arc<dyn DynTool>> {
+        self.position(name).map(|index| &self.tools[index])
+    }
+
+    /// Names of the registered tools, in registration order
+    pub fn names(&self) -> Vec<&str> {
+        self.tools.iter().map(|tool| tool.tool_name()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.tools.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.tools.is_empty()
+    }
+
+    /// Schemas of all tools, for `ChatCompletionRequestOptions::tools`
+    pub fn schemas(&self) -> Vec<ChatCompletionTool> {
+        self.tools.iter().map(|tool| tool.tool_schema()).collect()
+    }
+
+    /// Call the tool named `name` with JSON arguments
+    pub async fn call(
+        &self,
+        name: &str,
+        arguments: Value,
+    ) -> std::result::Result<Value, ToolError> {
+        let tool = self
+            .get(name)
+            .ok_or_else(|| ToolError::NotFound(name.to_string()))?;
+        tool.call(arguments).await
+    }
+
+    /// Run a tool call made by the model, parsing its JSON-encoded arguments
+    pub async fn execute(
+        &self,
+        tool_call: &ChatCompletionMessageToolCall,
+    ) -> std::result::Result<Value, ToolError> {
+        let arguments = serde_json::from_str(&tool_call.function.arguments)
+            .map_err(|e| ToolError::Schema(e.to_string()))?;
+        self.call(&tool_call.function.name, arguments).await
+    }
+
+    /// Like `execute`, for a tool call made from within a graph run
+    pub async fn execute_with_context(
+        &self,
+        ctx: &Context,
+        tool_call: &ChatCompletionMessageToolCall,
+    ) -> std::result::Result<Value, ToolError> {
+        let arguments = serde_json::from_str(&tool_call.function.arguments)
+            .map_err(|e| ToolError::Schema(e.to_string()))?;
+        let tool = self
+            .get(&tool_call.function.name)
+            .ok_or_else(|| ToolError::NotFound(tool_call.function.name.clone()))?;
+        tool.call_with_context(ctx, arguments).await
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.tools.iter().position(|tool| tool.tool_name() == name)
+    }
+}
+
+impl Debug for ToolRegistry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        f.debug_struct("ToolRegistry")
+            .field("tools", &self.names())
+            .finish()
+    }
+}
@@ -0,0 +1,334 @@
+use crate::types::{SchemaViolation, ToolError};
+use serde_json::{Map, Value};
+
+/// Check tool arguments against a parameter schema, returning
+/// `ToolError::InvalidArguments` with every violation found
+pub fn validate_arguments(schema: &Value, arguments: &Value) -> Result<(), ToolError> {
+    let violations = schema_violations(schema, arguments);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(ToolError::InvalidArguments(violations))
+    }
+}
+
+/// Every place where `value` breaks `schema`.
+///
+/// Covers the JSON Schema keywords schemars emits for parameter types:
+/// `type`, `enum`, `const`, `properties`, `required`, `additionalProperties`,
+/// `items`, `allOf`, `anyOf`, `oneOf`, `not`, local `$ref`s and the numeric,
+/// length and size bounds. Other keywords, such as `pattern` and `format`,
+/// and references that cannot be resolved are not checked.
+pub fn schema_violations(schema: &Value, value: &Value) -> Vec<SchemaViolation> {
+    let mut validator = Validator {
+        root: schema,
+        violations: vec![],
+    };
+    validator.check(schema, value, "");
+    validator.violations
+}
+
+struct Validator<'a> {
+    root: &'a Value,
+    violations: Vec<SchemaViolation>,
+}
+
+impl Validator<'_> {
+    fn check(&mut self, schema: &Value, value: &Value, path: &str) {
+        let schema = match schema {
+            Value::Bool(false) => {
+                return self.violation(path, "false", "no value is allowed here".to_string());
+            }
+            Value::Object(schema) => schema,
+            _ => return,
+        };
+
+        if let Some(target) = schema
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|reference| reference.strip_prefix('#'))
+            .and_then(|pointer| self.root.pointer(pointer))
+        {
+            self.check(target, value, path);
+        }
+
+        if let Some(types) = schema.get("type") {
+            let types: Vec<&str> = match types {
+                Value::String(name) => vec![name.as_str()],
+                Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+                _ => vec![],
+            };
+            if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+                // Further checks would only repeat the mismatch
+                return self.violation(
+                    path,
+                    "type",
+                    format!("expected {}, got {}", types.join(" or "), type_name(value)),
+                );
+            }
+        }
+
+        if let Some(Value::Array(allowed)) = schema.get("enum") {
+            if !allowed.contains(value) {
+                self.violation(
+                    path,
+                    "enum",
+                    format!("expected one of {}", Value::Array(allowed.clone())),
+                );
+            }
+        }
+        if let Some(expected) = schema.get("const") {
+            if expected != value {
+                self.violation(path, "const", format!("expected {}", expected));
+            }
+        }
+
+        match value {
+            Value::Number(number) => self.check_number(schema, number.as_f64(), path),
+            Value::String(string) => {
+                let length = string.chars().count() as u64;
+                if let Some(min) = bound(schema, "minLength").filter(|min| length < *min) {
+                    self.violation(
+                        path,
+                        "minLength",
+                        format!("must be at least {} characters", min),
+                    );
+                }
+                if let Some(max) = bound(schema, "maxLength").filter(|max| length > *max) {
+                    self.violation(
+                        path,
+                        "maxLength",
+                        format!("must be at most {} characters", max),
+                    );
+                }
+            }
+            Value::Array(items) => self.check_array(schema, items, path),
+            Value::Object(object) => self.check_object(schema, object, path),
+            _ => {}
+        }
+
+        if let Some(Value::Array(schemas)) = schema.get("allOf") {
+            for schema in schemas {
+                self.check(schema, value, path);
+            }
+        }
+        if let Some(Value::Array(schemas)) = schema.get("anyOf") {
+            self.check_alternatives(schemas, value, path, "anyOf");
+        }
+        if let Some(Value::Array(schemas)) = schema.get("oneOf") {
+            self.check_alternatives(schemas, value, path, "oneOf");
+        }
+        if let Some(schema) = schema.get("not") {
+            if self.matches(schema, value) {
+                self.violation(
+                    path,
+                    "not",
+                    "matches a schema it must not match".to_string(),
+                );
+            }
+        }
+    }
+
+    fn check_number(&mut self, schema: &Map<String, Value>, number: Option<f64>, path: &str) {
+        let Some(number) = number else {
+            return;
+        };
+        let limit = |keyword: &str| schema.get(keyword).and_then(Value::as_f64);
+        if let Some(min) = limit("minimum").filter(|min| number < *min) {
+            self.violation(path, "minimum", format!("must be at least {}", min));
+        }
+        if let Some(max) = limit("maximum").filter(|max| number > *max) {
+            self.violation(path, "maximum", format!("must be at most {}", max));
+        }
+        if let Some(min) = limit("exclusiveMinimum").filter(|min| number <= *min) {
+            self.violation(
+                path,
+                "exclusiveMinimum",
+                format!("must be greater than {}", min),
+            );
+        }
+        if let Some(max) = limit("exclusiveMaximum").filter(|max| number >= *max) {
+            self.violation(
+                path,
+                "exclusiveMaximum",
+                format!("must be less than {}", max),
+            );
+        }
+    }
+
+    fn check_array(&mut self, schema: &Map<String, Value>, items: &[Value], path: &str) {
+        match schema.get("items") {
+            // Tuples list one schema per position
+            Some(Value::Array(schemas)) => {
+                for (index, (schema, item)) in schemas.iter().zip(items).enumerate() {
+                    self.check(schema, item, &format!("{}/{}", path, index));
+                }
+            }
+            Some(schema) => {
+                for (index, item) in items.iter().enumerate() {
+                    self.check(schema, item, &format!("{}/{}", path, index));
+                }
+            }
+            None => {}
+        }
+        let length = items.len() as u64;
+        if let Some(min) = bound(schema, "minItems").filter(|min| length < *min) {
+            self.violation(
+                path,
+                "minItems",
+                format!("must have at least {} items", min),
+            );
+        }
+        if let Some(max) = bound(schema, "maxItems").filter(|max| length > *max) {
+            self.violation(path, "maxItems", format!("must have at most {} items", max));
+        }
+        let unique = schema.get("uniqueItems") == Some(&Value::Bool(true));
+        if unique && (1..items.len()).any(|index| items[..index].contains(&items[index])) {
+            self.violation(
+                path,
+                "uniqueItems",
+                "must not contain duplicates".to_string(),
+            );
+        }
+    }
+
+    fn check_object(
+        &mut self,
+        schema: &Map<String, Value>,
+        object: &Map<String, Value>,
+        path: &str,
+    ) {
+        if let Some(Value::Array(required)) = schema.get("required") {
+            for name in required.iter().filter_map(Value::as_str) {
+                if !object.contains_key(name) {
+                    self.violation(
+                        &property_path(path, name),
+                        "required",
+                        "missing required property".to_string(),
+                    );
+                }
+            }
+        }
+
+        let properties = schema.get("properties").and_then(Value::as_object);
+        for (name, value) in object {
+            let path = property_path(path, name);
+            match (
+                properties.and_then(|properties| properties.get(name)),
+                schema.get("additionalProperties"),
+            ) {
+                (Some(schema), _) => self.check(schema, value, &path),
+                (None, Some(Value::Bool(false))) => self.violation(
+                    &path,
+                    "additionalProperties",
+                    "unknown property".to_string(),
+                ),
+                (None, Some(schema)) => self.check(schema, value, &path),
+                (None, None) => {}
+            }
+        }
+    }
+
+    /// `anyOf` needs at least one matching schema and `oneOf` exactly one.
+    /// When all but one alternative reject the value's type outright, the
+    /// remaining one's violations are reported as they are more specific.
+    fn check_alternatives(&mut self, schemas: &[Value], value: &Value, path: &str, keyword: &str) {
+        let results: Vec<Vec<SchemaViolation>> = schemas
+            .iter()
+            .map(|schema| {
+                let mut validator = Validator {
+                    root: self.root,
+                    violations: vec![],
+                };
+                validator.check(schema, value, path);
+                validator.violations
+            })
+            .collect();
+        let matching = results
+            .iter()
+            .filter(|violations| violations.is_empty())
+            .count();
+        if matching == 1 || (matching > 1 && keyword == "anyOf") {
+            return;
+        }
+        if matching > 1 {
+            return self.violation(
+                path,
+                keyword,
+                format!("matches {} schemas, expected exactly one", matching),
+            );
+        }
+
+        let wrong_type = |violations: &Vec<SchemaViolation>| {
+            violations
+                .iter()
+                .any(|violation| violation.path == path && violation.constraint == "type")
+        };
+        let mut candidates = results
+            .into_iter()
+            .filter(|violations| !wrong_type(violations));
+        match (candidates.next(), candidates.next()) {
+            (Some(violations), None) => self.violations.extend(violations),
+            _ => self.violation(
+                path,
+                keyword,
+                "does not match any of the allowed schemas".to_string(),
+            ),
+        }
+    }
+
+    fn matches(&self, schema: &Value, value: &Value) -> bool {
+        let mut validator = Validator {
+            root: self.root,
+            violations: vec![],
+        };
+        validator.check(schema, value, "");
+        validator.violations.is_empty()
+    }
+
+    fn violation(&mut self, path: &str, constraint: &str, message: String) {
+        self.violations.push(SchemaViolation {
+            path: path.to_string(),
+            constraint: constraint.to_string(),
+            message,
+        });
+    }
+}
+
+fn bound(schema: &Map<String, Value>, keyword: &str) -> Option<u64> {
+    schema.get(keyword).and_then(Value::as_u64)
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "null" => value.is_null(),
+        "boolean" => value.is_boolean(),
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(number) if number.is_f64() => "number",
+        Value::Number(_) => "integer",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+// JSON pointer to a property, escaping `~` and `/` in its name
+fn property_path(path: &str, name: &str) -> String {
+    format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1"))
+}
@@ -14,6 +14,92 @@ pub enum ToolError {
 
     #[error("Serialization: {0}")]
     Serialization(String),
+
+    #[error("Tool not found: {0}")]
+    NotFound(String),
+
+    #[error("Invalid arguments:{}", list_violations(.0))]
+    InvalidArguments(Vec<SchemaViolation>),
+}
+
+/// A value in tool arguments that breaks the tool's parameter schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the value, empty for the arguments themselves
+    pub path: String,
+    /// Schema keyword that failed, such as `type` or `required`
+    pub constraint: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = if self.path.is_empty() {
+            "/"
+        } else {
+            &self.path
+        };
+        write!(f, "{}: {} ({})", path, self.message, self.constraint)
+    }
+}
+
+fn list_violations(violations: &[SchemaViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| format!("\n- {}", violation))
+        .collect()
+}
+
+/// Error type for store operations
+#[derive(Error, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "message")]
+pub enum StoreError {
+    #[error("Store IO: {0}")]
+    Io(String),
+
+    #[error("Serialization: {0}")]
+    Serialization(String),
+
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+}
+
+impl From<std::io::Error> for StoreError {
+    fn from(err: std::io::Error) -> Self {
+        StoreError::Io(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for StoreError {
+    fn from(err: serde_json::Error) -> Self {
+        StoreError::Serialization(err.to_string())
+    }
+}
+
+/// Error type for the update log
+#[derive(Error, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", content = "message")]
+pub enum UpdateLogError {
+    #[error("Update log IO: {0}")]
+    Io(String),
+
+    #[error("Serialization: {0}")]
+    Serialization(String),
+
+    #[error("Run {0} has changes but no start record")]
+    MissingStart(String),
+}
+
+impl From<std::io::Error> for UpdateLogError {
+    fn from(err: std::io::Error) -> Self {
+        UpdateLogError::Io(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for UpdateLogError {
+    fn from(err: serde_json::Error) -> Self {
+        UpdateLogError::Serialization(err.to_string())
+    }
 }
 
 /// Error type for node operations
@@ -26,6 +112,9 @@ pub enum NodeError {
     #[error(transparent)]
     Tool(#[from] ToolError),
 
+    #[error(transparent)]
+    Store(#[from] StoreError),
+
     #[error("Model: {0}")]
     ModelError(String),
 
@@ -68,6 +157,9 @@ pub enum GraphError {
     #[error(transparent)]
     Node(#[from] NodeError),
 
+    #[error(transparent)]
+    UpdateLog(#[from] UpdateLogError),
+
     #[error("Model: {0}")]
     ModelError(String),
 
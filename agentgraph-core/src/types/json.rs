@@ -0,0 +1,49 @@
+use crate::GraphState;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Untyped graph state backed by a JSON value.
+///
+/// Updates are JSON merge patches, so nodes only need to send the fields they
+/// change. Useful for graphs defined at run time rather than in Rust code.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct JsonState(pub Value);
+
+impl JsonState {
+    /// Look up a value by JSON pointer, e.g. `/messages/0/content`
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        self.0.pointer(pointer)
+    }
+}
+
+impl GraphState for JsonState {
+    type Update = Value;
+
+    fn apply(&mut self, update: Self::Update) {
+        merge_patch(&mut self.0, update);
+    }
+}
+
+/// Apply a JSON merge patch (RFC 7396) to `target`.
+///
+/// Objects are merged key by key, `null` removes a key, and any other value
+/// (including arrays) replaces the target outright.
+pub fn merge_patch(target: &mut Value, patch: Value) {
+    match patch {
+        Value::Object(patch) => {
+            if !target.is_object() {
+                *target = Value::Object(Default::default());
+            }
+            let target = target.as_object_mut().unwrap();
+            for (key, value) in patch {
+                if value.is_null() {
+                    target.remove(&key);
+                } else {
+                    merge_patch(target.entry(key).or_insert(Value::Null), value);
+                }
+            }
+        }
+        patch => *target = patch,
+    }
+}
@@ -1,8 +1,10 @@
 mod error;
+mod json;
 mod result;
 mod state;
 mod tests;
 
-pub use error::{GraphError, NodeError, ToolError};
+pub use error::{GraphError, NodeError, SchemaViolation, StoreError, ToolError, UpdateLogError};
+pub use json::{merge_patch, JsonState};
 pub use result::{GraphResult, NodeOutput, NodeResult};
-pub use state::GraphState;
+pub use state::{GraphState, Predicate, StateDiff, StateSchema};
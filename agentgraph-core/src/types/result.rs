@@ -12,6 +12,27 @@ where
 
     /// The node has produced zero or more updates to the existing state.
     Updates(Vec<S::Update>),
+
+    /// The node has produced updates and chosen the next node itself, bypassing
+    /// its outgoing edge. `goto` must name a node of the graph, one of the
+    /// node's declared destinations if it has any, or `END` to finish the run.
+    Command {
+        updates: Vec<S::Update>,
+        goto: String,
+    },
+}
+
+impl<S> NodeOutput<S>
+where
+    S: GraphState,
+{
+    /// Create a `Command` output that applies `updates` and routes to `goto`.
+    pub fn goto(updates: Vec<S::Update>, goto: impl Into<String>) -> Self {
+        NodeOutput::Command {
+            updates,
+            goto: goto.into(),
+        }
+    }
 }
 
 pub type NodeResult<S> = Result<NodeOutput<S>, NodeError>;
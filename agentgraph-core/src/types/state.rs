@@ -1,4 +1,5 @@
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
 
 pub trait GraphState: Debug + Send + Sync + Clone + 'static {
     type Update;
@@ -12,3 +13,51 @@ pub trait GraphState: Debug + Send + Sync + Clone + 'static {
         }
     }
 }
+
+/// A shareable test on values, carried by generated `Remove*` update variants
+pub struct Predicate<T: ?Sized>(Arc<dyn Fn(&T) -> bool + Send + Sync>);
+
+impl<T: ?Sized> Predicate<T> {
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&T) -> bool + Send + Sync + 'static,
+    {
+        Self(Arc::new(f))
+    }
+
+    /// Whether the value matches
+    pub fn matches(&self, value: &T) -> bool {
+        (self.0)(value)
+    }
+}
+
+impl<T: ?Sized> Clone for Predicate<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T: ?Sized> Debug for Predicate<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("Predicate(..)")
+    }
+}
+
+/// States that can compute the updates turning one value into another.
+/// Implemented by `#[derive(State)]` with `#[state(diff)]`, which also adds a
+/// `Set*` update variant for every field not using `replace`, used when the
+/// field's strategy cannot express a change.
+pub trait StateDiff: GraphState {
+    /// Updates that, applied to `self`, produce `other`
+    fn diff(&self, other: &Self) -> Vec<Self::Update>;
+}
+
+/// States that describe themselves and their updates with JSON Schema.
+/// Implemented by `#[derive(State)]` with `#[state(schema)]`, for state
+/// structs that also derive `schemars::JsonSchema`.
+pub trait StateSchema: GraphState {
+    /// Schema of the state
+    fn state_schema() -> serde_json::Value;
+    /// Schema of the state's `Update` enum
+    fn update_schema() -> serde_json::Value;
+}
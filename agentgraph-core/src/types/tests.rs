@@ -1,7 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use crate::completion::{chat_messages, Message};
+    use crate::types::{JsonState, Predicate, StateDiff, StateSchema};
     use crate::*;
     use agentgraph_macros::State;
+    use std::collections::{HashMap, HashSet};
 
     #[derive(State, Debug, Clone)]
     struct CounterState {
@@ -40,4 +43,340 @@ mod tests {
         ));
         assert_eq!(state.operations, vec!["increment", "decrement"]);
     }
+
+    fn keep_longest(current: &mut String, value: String) {
+        if value.len() > current.len() {
+            *current = value;
+        }
+    }
+
+    #[derive(State, Debug, Clone, Default)]
+    struct ScoreState {
+        #[update(add)]
+        total: i32,
+
+        #[update(max)]
+        best: i32,
+
+        #[update(min)]
+        worst: i32,
+
+        #[update(merge)]
+        scores: HashMap<String, i32>,
+
+        #[update(union)]
+        players: HashSet<String>,
+
+        #[update(with = keep_longest)]
+        longest_name: String,
+    }
+
+    #[test]
+    fn test_score_state_reducers() {
+        let mut state = ScoreState::default();
+        state.apply_many(vec![
+            ScoreStateUpdate::Total(3),
+            ScoreStateUpdate::Total(4),
+            ScoreStateUpdate::Best(5),
+            ScoreStateUpdate::Best(2),
+            ScoreStateUpdate::Worst(-1),
+            ScoreStateUpdate::Worst(1),
+            ScoreStateUpdate::Scores(HashMap::from([("a".into(), 1), ("b".into(), 2)])),
+            ScoreStateUpdate::Scores(HashMap::from([("a".into(), 3)])),
+            ScoreStateUpdate::Players(HashSet::from(["ann".into(), "bob".into()])),
+            ScoreStateUpdate::Players(HashSet::from(["bob".into(), "cat".into()])),
+            ScoreStateUpdate::LongestName("bob".into()),
+            ScoreStateUpdate::LongestName("annabel".into()),
+            ScoreStateUpdate::LongestName("cat".into()),
+        ]);
+
+        assert_eq!(state.total, 7);
+        assert_eq!(state.best, 5);
+        assert_eq!(state.worst, -1);
+        assert_eq!(
+            state.scores,
+            HashMap::from([("a".into(), 3), ("b".into(), 2)])
+        );
+        assert_eq!(state.players.len(), 3);
+        assert_eq!(state.longest_name, "annabel");
+    }
+
+    #[derive(State, Debug, Clone, Default)]
+    struct ChatState {
+        #[update(messages)]
+        messages: Vec<Message>,
+    }
+
+    #[test]
+    fn test_messages_reducer() {
+        use async_openai::types::{
+            ChatCompletionRequestAssistantMessage, ChatCompletionRequestUserMessage,
+        };
+
+        let mut state = ChatState::default();
+        state.apply(ChatStateUpdate::Messages(vec![
+            Message::with_id("q", ChatCompletionRequestUserMessage::from("hi")),
+            Message::with_id("tool", ChatCompletionRequestUserMessage::from("stale")),
+            Message::with_id("a", ChatCompletionRequestAssistantMessage::from("draft")),
+        ]));
+        state.apply(ChatStateUpdate::Messages(vec![
+            Message::with_id("a", ChatCompletionRequestAssistantMessage::from("hello")),
+            Message::remove("tool"),
+            Message::remove("missing"),
+        ]));
+
+        let ids: Vec<&str> = state.messages.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, ["q", "a"]);
+        assert_eq!(
+            chat_messages(&state.messages)[1],
+            ChatCompletionRequestAssistantMessage::from("hello").into()
+        );
+    }
+
+    #[derive(State, Debug, Clone, Default)]
+    struct InventoryState {
+        #[update(append, clear, remove)]
+        log: Vec<String>,
+
+        #[update(merge, remove, insert)]
+        stock: HashMap<String, u32>,
+
+        #[update(clear)]
+        note: String,
+    }
+
+    #[test]
+    fn test_extra_update_variants() {
+        let mut state = InventoryState::default();
+        state.apply_many(vec![
+            InventoryStateUpdate::Log(vec!["add apples".into(), "add pears".into()]),
+            InventoryStateUpdate::Stock(HashMap::from([("apple".into(), 3), ("pear".into(), 1)])),
+            InventoryStateUpdate::InsertStock("plum".into(), 7),
+            InventoryStateUpdate::RemoveStock(vec!["pear".into(), "kiwi".into()]),
+            InventoryStateUpdate::RemoveLog(Predicate::new(|entry: &String| {
+                entry.contains("pears")
+            })),
+            InventoryStateUpdate::Note("restocked".into()),
+        ]);
+        assert_eq!(state.log, vec!["add apples"]);
+        assert_eq!(
+            state.stock,
+            HashMap::from([("apple".into(), 3), ("plum".into(), 7)])
+        );
+        assert_eq!(state.note, "restocked");
+
+        state.apply_many(vec![
+            InventoryStateUpdate::ClearLog,
+            InventoryStateUpdate::ClearNote,
+        ]);
+        assert!(state.log.is_empty());
+        assert!(state.note.is_empty());
+    }
+
+    #[derive(State, Debug, Clone, Default, PartialEq)]
+    #[state(diff)]
+    struct AuditState {
+        #[update(replace)]
+        status: String,
+
+        #[update(add)]
+        count: i32,
+
+        #[update(append, clear)]
+        events: Vec<String>,
+
+        #[update(merge, remove)]
+        scores: HashMap<String, i32>,
+
+        #[update(union)]
+        tags: HashSet<String>,
+
+        #[update(messages)]
+        messages: Vec<Message>,
+    }
+
+    #[test]
+    fn test_state_diff() {
+        use async_openai::types::ChatCompletionRequestUserMessage;
+
+        let before = AuditState {
+            status: "draft".into(),
+            count: 2,
+            events: vec!["created".into()],
+            scores: HashMap::from([("a".into(), 1), ("b".into(), 2)]),
+            tags: HashSet::from(["x".into()]),
+            messages: vec![
+                Message::with_id("1", ChatCompletionRequestUserMessage::from("hi")),
+                Message::with_id("2", ChatCompletionRequestUserMessage::from("stale")),
+            ],
+        };
+        assert!(before.diff(&before).is_empty());
+
+        let mut after = before.clone();
+        after.count = 5;
+        after.events.push("reviewed".into());
+        after.scores = HashMap::from([("a".into(), 3), ("c".into(), 1)]);
+        after.tags.insert("y".into());
+        after.messages.remove(1);
+
+        let updates = before.diff(&after);
+        assert!(matches!(updates[0], AuditStateUpdate::Count(3)));
+        assert!(matches!(&updates[1], AuditStateUpdate::Events(e) if e == &["reviewed"]));
+
+        let mut patched = before.clone();
+        patched.apply_many(updates);
+        assert_eq!(patched, after);
+
+        // A rewritten history falls back to clearing the field
+        let mut rewritten = before.clone();
+        rewritten.events = vec!["replaced".into()];
+        let updates = before.diff(&rewritten);
+        assert!(matches!(updates[0], AuditStateUpdate::ClearEvents));
+        let mut patched = before.clone();
+        patched.apply_many(updates);
+        assert_eq!(patched, rewritten);
+    }
+
+    #[derive(State, Debug, Clone, Default, PartialEq)]
+    #[state(diff)]
+    struct RoundTripState {
+        #[update(replace)]
+        status: String,
+
+        #[update(add)]
+        count: u32,
+
+        #[update(add)]
+        total: f64,
+
+        #[update(max)]
+        best: i64,
+
+        #[update(min)]
+        worst: i64,
+
+        #[update(with = keep_longest)]
+        longest: String,
+
+        #[update(append)]
+        log: Vec<String>,
+
+        #[update(merge)]
+        scores: HashMap<String, i32>,
+
+        #[update(union)]
+        tags: HashSet<String>,
+
+        #[update(messages)]
+        messages: Vec<Message>,
+    }
+
+    #[test]
+    fn test_state_diff_round_trip() {
+        use async_openai::types::ChatCompletionRequestUserMessage;
+
+        let a = RoundTripState {
+            status: "open".into(),
+            count: 5,
+            total: 0.1,
+            best: 5,
+            worst: -5,
+            longest: "longer".into(),
+            log: vec!["x".into()],
+            scores: HashMap::from([("a".into(), 1), ("b".into(), 2)]),
+            tags: HashSet::from(["x".into(), "y".into()]),
+            messages: vec![Message::with_id(
+                "1",
+                ChatCompletionRequestUserMessage::from("hi"),
+            )],
+        };
+        let b = RoundTripState {
+            status: "closed".into(),
+            count: 3,
+            total: 0.3,
+            best: 3,
+            worst: -3,
+            longest: "short".into(),
+            log: vec!["y".into()],
+            scores: HashMap::from([("a".into(), 4)]),
+            tags: HashSet::from(["z".into()]),
+            messages: vec![],
+        };
+
+        // Every strategy in both directions, plus no change at all
+        for (from, to) in [
+            (&a, &b),
+            (&b, &a),
+            (&a, &a),
+            (&RoundTripState::default(), &a),
+        ] {
+            let mut patched = from.clone();
+            patched.apply_many(from.diff(to));
+            assert_eq!(&patched, to);
+        }
+        assert!(a.diff(&a).is_empty());
+    }
+
+    #[derive(State, Debug, Clone, schemars::JsonSchema)]
+    #[state(schema)]
+    struct TicketState {
+        #[update(replace)]
+        title: String,
+
+        #[update(append, clear)]
+        labels: Vec<String>,
+
+        #[update(messages)]
+        messages: Vec<Message>,
+    }
+
+    #[test]
+    fn test_state_schema() {
+        let state = TicketState::state_schema();
+        assert_eq!(state["type"], "object");
+        assert_eq!(state["properties"]["title"]["type"], "string");
+        assert_eq!(state["properties"]["labels"]["type"], "array");
+
+        let update = TicketState::update_schema();
+        let variants: Vec<&serde_json::Value> = update["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|variant| variant.get("enum").unwrap_or(&variant["required"]))
+            .collect();
+        assert_eq!(
+            variants,
+            [
+                &serde_json::json!(["ClearLabels"]),
+                &serde_json::json!(["Title"]),
+                &serde_json::json!(["Labels"]),
+                &serde_json::json!(["Messages"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_state_merge_patch() {
+        let mut state = JsonState(serde_json::json!({
+            "count": 1,
+            "user": { "name": "Ryan", "email": "ryan@example.com" },
+            "tags": ["a"]
+        }));
+        state.apply_many(vec![
+            serde_json::json!({ "count": 2, "user": { "email": null } }),
+            serde_json::json!({ "tags": ["b"] }),
+        ]);
+        assert_eq!(
+            state,
+            JsonState(serde_json::json!({
+                "count": 2,
+                "user": { "name": "Ryan" },
+                "tags": ["b"]
+            }))
+        );
+        assert_eq!(
+            state.pointer("/user/name"),
+            Some(&serde_json::json!("Ryan"))
+        );
+    }
 }
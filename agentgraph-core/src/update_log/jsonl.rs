@@ -0,0 +1,80 @@
+use super::{RecordWrite, UpdateRecord, UpdateSink};
+use crate::types::{GraphState, UpdateLogError};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Update log appended to a file, one JSON record per line. Every record is
+/// written through to disk before the change is applied, on Tokio's
+/// blocking pool.
+#[derive(Debug)]
+pub struct JsonlSink {
+    path: PathBuf,
+    file: Arc<Mutex<File>>,
+}
+
+impl JsonlSink {
+    /// Open `path` for appending, creating it if needed
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, UpdateLogError> {
+        let path = path.into();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self {
+            path,
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read every record from a log file, e.g. to pass to `replay`
+    pub fn read<S>(
+        path: impl AsRef<Path>,
+    ) -> Result<Vec<UpdateRecord<S::Update, S>>, UpdateLogError>
+    where
+        S: GraphState + DeserializeOwned,
+        S::Update: DeserializeOwned,
+    {
+        let reader = BufReader::new(File::open(path)?);
+        let mut records = vec![];
+        for line in reader.lines() {
+            let line = line?;
+            if !line.trim().is_empty() {
+                records.push(serde_json::from_str(&line)?);
+            }
+        }
+        Ok(records)
+    }
+}
+
+impl<S> UpdateSink<S> for JsonlSink
+where
+    S: GraphState + Serialize,
+    S::Update: Serialize,
+{
+    fn record(&self, record: &UpdateRecord<&S::Update, &S>) -> RecordWrite {
+        let line = serde_json::to_vec(record).map(|mut line| {
+            line.push(b'\n');
+            line
+        });
+        let file = self.file.clone();
+        Box::pin(async move {
+            let line = line?;
+            tokio::task::spawn_blocking(move || {
+                let mut file = file
+                    .lock()
+                    .map_err(|_| UpdateLogError::Io("Update log lock poisoned".to_string()))?;
+                file.write_all(&line)?;
+                file.flush()?;
+                file.sync_data()?;
+                Ok(())
+            })
+            .await
+            .map_err(|e| UpdateLogError::Io(e.to_string()))?
+        })
+    }
+}
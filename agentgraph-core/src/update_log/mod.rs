@@ -0,0 +1,100 @@
+//! Write-ahead log of the state changes applied while running a graph.
+//!
+//! A graph with an update sink records the initial state of every run, then
+//! every change before applying it, so replaying a run's records rebuilds
+//! its final state. Records carry a run id, so runs sharing a sink can be
+//! told apart. Derive serializable update enums with `#[state(serde)]`.
+
+mod jsonl;
+mod tests;
+
+use crate::types::{GraphState, UpdateLogError};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+pub use jsonl::JsonlSink;
+
+/// A state change made by a node, or the state a run started from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+pub enum Change<U, S> {
+    /// The initial state, recorded once when a run starts
+    Start(S),
+    /// An update from `NodeOutput::Updates` or `NodeOutput::Command`
+    Update(U),
+    /// A whole new state from `NodeOutput::Full`
+    Full(S),
+}
+
+/// One entry of the update log. Sinks receive borrowed records,
+/// `UpdateRecord<&S::Update, &S>`, and reading a log yields owned ones.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpdateRecord<U, S> {
+    pub timestamp: DateTime<Utc>,
+    /// Id shared by all records of one `Graph::run` call
+    pub run_id: String,
+    /// Trace id of the context the node ran with
+    pub trace_id: String,
+    /// Node that produced the change, `START` for the start record
+    pub node: String,
+    /// Attempt of the node that succeeded, starting at 1, or 0 for the start
+    /// record
+    pub attempt: usize,
+    pub change: Change<U, S>,
+}
+
+/// Destination for the update log of a graph
+pub trait UpdateSink<S: GraphState>: Send + Sync + Debug {
+    /// Record a change before it takes effect. The record is only borrowed
+    /// for this call, so sinks encode it right away and return a future that
+    /// writes it, which the run awaits before moving on. Blocking I/O belongs
+    /// on the blocking pool. An error fails the run.
+    fn record(&self, record: &UpdateRecord<&S::Update, &S>) -> RecordWrite;
+}
+
+/// Pending write of an update record, see `UpdateSink::record`
+pub type RecordWrite = BoxFuture<'static, Result<(), UpdateLogError>>;
+
+/// Rebuild the final state of every run in a log, in the order the runs
+/// started. Records of concurrent runs may be interleaved.
+pub fn replay<S, I>(records: I) -> Result<Vec<(String, S)>, UpdateLogError>
+where
+    S: GraphState,
+    I: IntoIterator<Item = UpdateRecord<S::Update, S>>,
+{
+    let mut runs: Vec<(String, S)> = vec![];
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for UpdateRecord { run_id, change, .. } in records {
+        if let Change::Start(initial_state) = change {
+            index.insert(run_id.clone(), runs.len());
+            runs.push((run_id, initial_state));
+            continue;
+        }
+        let position = *index
+            .get(&run_id)
+            .ok_or(UpdateLogError::MissingStart(run_id))?;
+        let state = &mut runs[position].1;
+        match change {
+            Change::Update(update) => state.apply(update),
+            Change::Full(full) => *state = full,
+            Change::Start(_) => {}
+        }
+    }
+    Ok(runs)
+}
+
+/// Rebuild the final state of the run `run_id`
+pub fn replay_run<S, I>(records: I, run_id: &str) -> Result<S, UpdateLogError>
+where
+    S: GraphState,
+    I: IntoIterator<Item = UpdateRecord<S::Update, S>>,
+{
+    let records = records.into_iter().filter(|record| record.run_id == run_id);
+    replay(records)?
+        .pop()
+        .map(|(_, state)| state)
+        .ok_or_else(|| UpdateLogError::MissingStart(run_id.to_string()))
+}
@@ -0,0 +1,132 @@
+#[cfg(test)]
+mod tests {
+    use crate::update_log::{replay, replay_run, Change, JsonlSink, UpdateSink};
+    use crate::*;
+    use agentgraph_macros::State;
+    use serde::{Deserialize, Serialize};
+    use std::sync::Arc;
+
+    #[derive(State, Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[state(serde)]
+    struct LedgerState {
+        #[update(add)]
+        balance: i64,
+
+        #[update(append)]
+        entries: Vec<String>,
+    }
+
+    #[tokio::test]
+    async fn test_update_log_replay() {
+        let deposit = FunctionNode::new("deposit", |_ctx, _state: LedgerState| async move {
+            Ok(NodeOutput::Updates(vec![
+                LedgerStateUpdate::Balance(100),
+                LedgerStateUpdate::Entries(vec!["deposit 100".into()]),
+            ]))
+        });
+        let reset = FunctionNode::new("reset", |_ctx, state: LedgerState| async move {
+            Ok(NodeOutput::Full(LedgerState {
+                balance: state.balance,
+                entries: vec![],
+            }))
+        });
+        let withdraw = FunctionNode::new("withdraw", |_ctx, _state: LedgerState| async move {
+            Ok(NodeOutput::Updates(vec![LedgerStateUpdate::Balance(-30)]))
+        });
+
+        let path = std::env::temp_dir().join(format!("{}.jsonl", uuid::Uuid::new_v4()));
+        let sink: Arc<dyn UpdateSink<LedgerState>> = Arc::new(JsonlSink::open(&path).unwrap());
+        let built_graph = {
+            let mut graph = Graph::new("ledger");
+            graph
+                .add_node(deposit)
+                .add_node(reset)
+                .add_node(withdraw)
+                .add_edge(START, "deposit")
+                .add_edge("deposit", "reset")
+                .add_edge("reset", "withdraw")
+                .add_edge("withdraw", END)
+                .set_update_sink(sink);
+            graph.build()
+        };
+
+        let initial = LedgerState {
+            balance: 0,
+            entries: vec![],
+        };
+        let ctx = Context::new("audit");
+        let result = built_graph.run(&ctx, initial.clone()).await.unwrap();
+        assert_eq!(result.balance, 70);
+
+        let records = JsonlSink::read::<LedgerState>(&path).unwrap();
+        let nodes: Vec<&str> = records.iter().map(|r| r.node.as_str()).collect();
+        assert_eq!(nodes, [START, "deposit", "deposit", "reset", "withdraw"]);
+        assert!(matches!(&records[0].change, Change::Start(state) if state == &initial));
+        assert!(records[1..]
+            .iter()
+            .all(|r| r.attempt == 1 && r.trace_id == "audit"));
+        assert!(matches!(records[3].change, Change::Full(_)));
+        let run_id = records[0].run_id.clone();
+        assert!(records.iter().all(|r| r.run_id == run_id));
+
+        // The log alone is enough to rebuild the run
+        assert_eq!(replay_run(records, &run_id).unwrap(), result);
+
+        // Concurrent runs sharing the sink are told apart by run id
+        let initials: Vec<LedgerState> = (0..4)
+            .map(|balance| LedgerState {
+                balance,
+                entries: vec![],
+            })
+            .collect();
+        let results: Vec<LedgerState> = built_graph
+            .run_batch(&ctx, initials, 4)
+            .await
+            .into_iter()
+            .map(Result::unwrap)
+            .collect();
+        let runs = replay(JsonlSink::read::<LedgerState>(&path).unwrap()).unwrap();
+        assert_eq!(runs.len(), 5);
+        let mut replayed: Vec<i64> = runs[1..].iter().map(|(_, s)| s.balance).collect();
+        let mut expected: Vec<i64> = results.iter().map(|s| s.balance).collect();
+        replayed.sort();
+        expected.sort();
+        assert_eq!(replayed, expected);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_log_skips_rejected_jump() {
+        let jump = FunctionNode::new("jump", |_ctx, _state: LedgerState| async move {
+            Ok(NodeOutput::Command {
+                updates: vec![LedgerStateUpdate::Balance(10)],
+                goto: "withdraw".to_string(),
+            })
+        });
+        let withdraw = FunctionNode::new("withdraw", |_ctx, _state: LedgerState| async move {
+            Ok(NodeOutput::Updates(vec![LedgerStateUpdate::Balance(-30)]))
+        });
+
+        let path = std::env::temp_dir().join(format!("{}.jsonl", uuid::Uuid::new_v4()));
+        let mut graph = Graph::new("jumps");
+        graph
+            .add_node(jump)
+            .add_node(withdraw)
+            .add_edge(START, "jump")
+            .add_destinations("jump", [END])
+            .add_edge("withdraw", END)
+            .set_update_sink(Arc::new(JsonlSink::open(&path).unwrap()));
+        let initial = LedgerState {
+            balance: 0,
+            entries: vec![],
+        };
+        let result = graph.build().run(&Context::new("jumps"), initial).await;
+        assert!(matches!(result, Err(GraphError::InvalidTransition(_))));
+
+        // Only the start record, not the rejected update
+        let records = JsonlSink::read::<LedgerState>(&path).unwrap();
+        assert_eq!(records.len(), 1);
+        assert!(matches!(records[0].change, Change::Start(_)));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,5 +1,6 @@
 use agentgraph_core::prelude::*;
-use agentgraph_macros::tool;
+use agentgraph_core::tool::{schema_violations, validate_arguments, ChatCompletionToolType};
+use agentgraph_macros::{tool, State};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -172,3 +173,219 @@ fn test_optional_schema() {
     let optional = properties.get("optional").unwrap();
     assert_eq!(optional.get("required"), Some(&serde_json::json!(false)));
 }
+
+// Test exposing a graph as a tool
+#[derive(State, Debug, Clone)]
+struct SumState {
+    #[update(replace)]
+    x: i32,
+
+    #[update(replace)]
+    y: i32,
+
+    #[update(replace)]
+    sum: i32,
+}
+
+struct SumGraph;
+
+impl GraphToolSpec for SumGraph {
+    type State = SumState;
+    type Params = AddParams;
+    type Response = AddResponse;
+
+    fn name() -> &'static str {
+        "sum_graph"
+    }
+
+    fn description() -> &'static str {
+        "Adds two numbers with a graph"
+    }
+
+    fn initial_state(params: AddParams) -> SumState {
+        SumState {
+            x: params.x,
+            y: params.y,
+            sum: 0,
+        }
+    }
+
+    fn response(state: SumState) -> AddResponse {
+        AddResponse { sum: state.sum }
+    }
+}
+
+fn sum_graph() -> Graph<SumState, Built> {
+    let add = FunctionNode::new("add", |_ctx, state: SumState| async move {
+        Ok(NodeOutput::Updates(vec![SumStateUpdate::Sum(
+            state.x + state.y,
+        )]))
+    });
+    let mut graph = Graph::new("sum");
+    graph
+        .add_node(add)
+        .add_edge(START, "add")
+        .add_edge("add", END);
+    graph.build()
+}
+
+#[tokio::test]
+async fn test_graph_tool_execution() {
+    let tool = GraphTool::<SumGraph>::new(sum_graph());
+    let params = AddParams { x: 2, y: 40 };
+
+    let result = ToolFunction::execute(&tool, params).await.unwrap();
+    assert_eq!(result.sum, 42);
+}
+
+#[tokio::test]
+async fn test_graph_tool_uses_caller_context() {
+    let add = FunctionNode::new("add", |ctx: &Context, state: SumState| {
+        let offset = ctx.run_config.get::<i32>("offset").unwrap_or_default();
+        async move {
+            Ok(NodeOutput::Updates(vec![SumStateUpdate::Sum(
+                state.x + state.y + offset,
+            )]))
+        }
+    });
+    let mut graph = Graph::new("sum");
+    graph
+        .add_node(add)
+        .add_edge(START, "add")
+        .add_edge("add", END);
+    let registry = ToolRegistry::new().with(GraphTool::<SumGraph>::new(graph.build()));
+
+    let tool_call = async_openai::types::ChatCompletionMessageToolCall {
+        id: "call_1".to_string(),
+        r#type: ChatCompletionToolType::Function,
+        function: async_openai::types::FunctionCall {
+            name: "sum_graph".to_string(),
+            arguments: r#"{"x": 20, "y": 22}"#.to_string(),
+        },
+    };
+    let ctx = Context::new("caller").with_run_config(RunConfig::new().with("offset", 100));
+    let sum = registry
+        .execute_with_context(&ctx, &tool_call)
+        .await
+        .unwrap();
+    assert_eq!(sum, serde_json::json!({"sum": 142}));
+
+    // Without a caller's context the tool's own context is used
+    let sum = registry.execute(&tool_call).await.unwrap();
+    assert_eq!(sum, serde_json::json!({"sum": 42}));
+}
+
+#[test]
+fn test_graph_tool_schema() {
+    let schema = <GraphTool<SumGraph> as ToolFunction>::get_schema();
+
+    assert_eq!(schema.function.name, "sum_graph");
+    assert_eq!(
+        schema.function.description.unwrap(),
+        "Adds two numbers with a graph"
+    );
+    assert_eq!(schema.function.parameters.unwrap(), AddParams::schema());
+}
+
+#[tokio::test]
+async fn test_tool_registry() {
+    let registry = ToolRegistry::new()
+        .with(Add)
+        .with(HandleOptional)
+        .with(GraphTool::<SumGraph>::new(sum_graph()));
+
+    assert_eq!(registry.names(), ["add", "handle_optional", "sum_graph"]);
+    let schemas = registry.schemas();
+    assert_eq!(schemas[2].function.name, "sum_graph");
+
+    let sum = registry
+        .call("add", serde_json::json!({"x": 1, "y": 2}))
+        .await
+        .unwrap();
+    assert_eq!(sum, serde_json::json!({"sum": 3}));
+
+    let tool_call = async_openai::types::ChatCompletionMessageToolCall {
+        id: "call_1".to_string(),
+        r#type: ChatCompletionToolType::Function,
+        function: async_openai::types::FunctionCall {
+            name: "sum_graph".to_string(),
+            arguments: r#"{"x": 20, "y": 22}"#.to_string(),
+        },
+    };
+    let sum = registry.execute(&tool_call).await.unwrap();
+    assert_eq!(sum, serde_json::json!({"sum": 42}));
+
+    let missing = registry.call("divide", serde_json::json!({})).await;
+    assert!(matches!(missing, Err(ToolError::NotFound(name)) if name == "divide"));
+
+    let invalid = registry.call("add", serde_json::json!({"x": "one"})).await;
+    let Err(ToolError::InvalidArguments(violations)) = invalid else {
+        panic!("Expected invalid arguments");
+    };
+    assert_eq!(violations.len(), 2);
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(deny_unknown_fields)]
+struct SearchParams {
+    query: String,
+    #[schemars(range(min = 1, max = 10))]
+    limit: Option<u8>,
+    sort: Sort,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Sort {
+    Relevance,
+    Date,
+}
+
+#[test]
+fn test_argument_validation() {
+    let schema = <SearchParams as JsonSchema>::schema();
+    let valid = serde_json::json!({
+        "query": "rust",
+        "limit": null,
+        "sort": "date",
+        "tags": ["lang"]
+    });
+    assert!(validate_arguments(&schema, &valid).is_ok());
+
+    let invalid = serde_json::json!({
+        "limit": 20,
+        "sort": "newest",
+        "tags": ["lang", 1],
+        "page": 2
+    });
+    let violations = schema_violations(&schema, &invalid);
+    let found: Vec<(&str, &str)> = violations
+        .iter()
+        .map(|violation| (violation.path.as_str(), violation.constraint.as_str()))
+        .collect();
+    assert_eq!(found.len(), 5);
+    for expected in [
+        ("/query", "required"),
+        ("/limit", "maximum"),
+        ("/sort", "enum"),
+        ("/tags/1", "type"),
+        ("/page", "additionalProperties"),
+    ] {
+        assert!(
+            found.contains(&expected),
+            "missing {:?} in {:?}",
+            expected,
+            found
+        );
+    }
+
+    let error = validate_arguments(&schema, &serde_json::json!({"query": 1})).unwrap_err();
+    assert_eq!(
+        error.to_string(),
+        "Invalid arguments:\n\
+         - /sort: missing required property (required)\n\
+         - /tags: missing required property (required)\n\
+         - /query: expected string, got integer (type)"
+    );
+}
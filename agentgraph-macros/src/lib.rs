@@ -1,5 +1,6 @@
 use proc_macro::TokenStream;
 
+mod node;
 mod state;
 mod tool;
 mod tools;
@@ -14,7 +15,12 @@ pub fn tools(attr: TokenStream, item: TokenStream) -> TokenStream {
     tools::tools_impl(attr, item)
 }
 
-#[proc_macro_derive(State, attributes(update))]
+#[proc_macro_attribute]
+pub fn node(attr: TokenStream, item: TokenStream) -> TokenStream {
+    node::node_impl(attr, item)
+}
+
+#[proc_macro_derive(State, attributes(update, state))]
 pub fn derive_state(input: TokenStream) -> TokenStream {
     state::derive_state_impl(input)
 }
@@ -0,0 +1,3 @@
+mod node;
+
+pub use node::node_impl;
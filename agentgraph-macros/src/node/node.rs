@@ -0,0 +1,122 @@
+use proc_macro::TokenStream;
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, Error, FnArg, ItemFn, LitStr, Receiver, Type};
+
+/// Entry point for the `#[node]` / `#[node(name = "...")]` attribute macro.
+///
+/// Keeps the function as written and adds a `<fn>_node` constructor returning
+/// a `BoundNode` that calls it. Methods take `&self` and their constructor
+/// takes `self: Arc<Self>`, so one instance can back several nodes. Functions
+/// without `self` must be free functions, as the constructor calls them
+/// unqualified:
+/// ```ignore
+/// #[node(name = "agent")]
+/// async fn call(&self, ctx: &Context, state: AgentState) -> NodeResult<AgentState> { ... }
+/// // graph.add_node(Arc::new(agent).call_node());
+///
+/// #[node]
+/// async fn greet(ctx: &Context, state: AgentState) -> NodeResult<AgentState> { ... }
+/// // graph.add_node(greet_node());
+/// ```
+pub fn node_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let input_fn = parse_macro_input!(item as ItemFn);
+    let mut name = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("Expected `name = \"...\"`"))
+        }
+    });
+    parse_macro_input!(attr with parser);
+
+    match expand(&input_fn, name) {
+        Ok(expanded) => expanded.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn expand(input_fn: &ItemFn, name: Option<LitStr>) -> syn::Result<proc_macro2::TokenStream> {
+    let sig = &input_fn.sig;
+    let fn_name = &sig.ident;
+    let node_fn = format_ident!("{}_node", fn_name);
+    let vis = &input_fn.vis;
+    let name = name.unwrap_or_else(|| LitStr::new(&fn_name.to_string(), fn_name.span()));
+
+    if sig.asyncness.is_none() {
+        return Err(Error::new(sig.span(), "Node functions must be async"));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    let has_receiver = match sig.inputs.first() {
+        Some(FnArg::Receiver(receiver)) => {
+            check_receiver(receiver)?;
+            inputs.next();
+            true
+        }
+        _ => false,
+    };
+
+    let typed: Vec<&syn::PatType> = inputs
+        .map(|arg| match arg {
+            FnArg::Typed(pat_type) => Ok(pat_type),
+            FnArg::Receiver(receiver) => {
+                Err(Error::new(receiver.span(), "Unexpected self argument"))
+            }
+        })
+        .collect::<syn::Result<_>>()?;
+    let [_ctx, state] = typed.as_slice() else {
+        return Err(Error::new(
+            sig.inputs.span(),
+            "Node functions take `ctx: &Context, state: S` after an optional `&self`",
+        ));
+    };
+    let state_type = &state.ty;
+
+    let expanded = if has_receiver {
+        quote! {
+            #input_fn
+
+            /// Node calling this method on a shared `self`
+            #vis fn #node_fn(
+                self: ::std::sync::Arc<Self>,
+            ) -> ::agentgraph_core::node::BoundNode<::std::sync::Arc<Self>, #state_type> {
+                ::agentgraph_core::node::BoundNode::new(#name, self, |this, ctx, state| {
+                    ::std::boxed::Box::pin(this.#fn_name(ctx, state))
+                })
+            }
+        }
+    } else {
+        // Spanned so that using this on an associated function reports the
+        // unresolved call at the function's name
+        let call = quote_spanned! {fn_name.span()=> #fn_name(ctx, state) };
+        quote! {
+            #input_fn
+
+            /// Node calling this function
+            #vis fn #node_fn() -> ::agentgraph_core::node::BoundNode<(), #state_type> {
+                ::agentgraph_core::node::BoundNode::new(#name, (), |_, ctx, state| {
+                    ::std::boxed::Box::pin(#call)
+                })
+            }
+        }
+    };
+    Ok(expanded)
+}
+
+/// Only shared borrows of `self` are supported, since nodes run through `&self`
+fn check_receiver(receiver: &Receiver) -> syn::Result<()> {
+    let shared = match &*receiver.ty {
+        Type::Reference(reference) => reference.mutability.is_none(),
+        _ => false,
+    };
+    if shared {
+        Ok(())
+    } else {
+        Err(Error::new(
+            receiver.span(),
+            "Node methods must take &self (not mut or owned self)",
+        ))
+    }
+}
@@ -7,6 +7,7 @@ pub fn derive_state_impl(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
     let name = input.ident;
     let update_name = format_ident!("{}Update", name);
+    let options = parse_state_options(&input.attrs);
 
     // Extract fields and their update strategies
     let fields = match input.data {
@@ -20,37 +21,242 @@ pub fn derive_state_impl(input: TokenStream) -> TokenStream {
     // Generate update enum and implementations
     let mut update_variants = vec![];
     let mut update_match_arms = vec![];
+    let mut diff_blocks = vec![];
+    let mut messages_impl = None;
 
     for field in fields {
         let field_name = field.ident.unwrap();
         let field_type = field.ty;
 
         // Parse update attribute
-        let update_strategy = field
+        let UpdateAttr {
+            strategy: update_strategy,
+            extras,
+        } = field
             .attrs
             .iter()
             .find(|attr| attr.path().is_ident("update"))
-            .map(|attr| attr.parse_args::<syn::Ident>().unwrap())
-            .unwrap_or_else(|| syn::Ident::new("replace", proc_macro2::Span::call_site()));
+            .map(parse_update_attr)
+            .unwrap_or_default();
 
         let variant_name = format_ident!("{}", field_name.to_string().to_case(Case::Pascal));
         update_variants.push(quote! {
             #variant_name(#field_type)
         });
 
-        let update_impl = match update_strategy.to_string().as_str() {
-            "append" => quote! { self.#field_name.extend(value) },
-            "merge" => quote! { self.#field_name.extend(value.into_iter()) },
-            "replace" => quote! { self.#field_name = value },
-            strategy => panic!("Unknown update strategy: {}", strategy),
+        if is_messages_field(&field.attrs) {
+            // A plain appended history, or one of `Message`s merged by id
+            let (messages, append) = match &update_strategy {
+                Strategy::Builtin(strategy) if strategy == "append" => (
+                    quote! { self.#field_name.iter().collect() },
+                    quote! { messages },
+                ),
+                Strategy::Builtin(strategy) if strategy == "messages" => (
+                    quote! {
+                        self.#field_name
+                            .iter()
+                            .filter_map(|message| message.message.as_ref())
+                            .collect()
+                    },
+                    quote! {
+                        messages
+                            .into_iter()
+                            .map(::agentgraph_core::completion::Message::new)
+                            .collect()
+                    },
+                ),
+                _ => panic!(
+                    "`#[state(messages)]` needs an `append` or `messages` field: {}",
+                    field_name
+                ),
+            };
+            if messages_impl.is_some() {
+                panic!("Only one field can be marked `#[state(messages)]`");
+            }
+            messages_impl = Some(quote! {
+                impl ::agentgraph_core::prebuilt::HasMessages for #name {
+                    fn messages(
+                        &self,
+                    ) -> Vec<&::agentgraph_core::prebuilt::ChatCompletionRequestMessage> {
+                        #messages
+                    }
+
+                    fn append_messages(
+                        messages: Vec<::agentgraph_core::prebuilt::ChatCompletionRequestMessage>,
+                    ) -> Self::Update {
+                        #update_name::#variant_name(#append)
+                    }
+                }
+            });
+        }
+
+        let is_replace =
+            matches!(&update_strategy, Strategy::Builtin(strategy) if strategy == "replace");
+        if options.diff && !is_replace {
+            let set_name = set_variant(&variant_name);
+            update_variants.push(quote! { #set_name(#field_type) });
+            update_match_arms.push(quote! {
+                #update_name::#set_name(value) => { self.#field_name = value }
+            });
+        }
+
+        if options.diff {
+            diff_blocks.push(diff_field(
+                &update_name,
+                &variant_name,
+                &field_name,
+                &field_type,
+                &update_strategy,
+                &extras,
+            ));
+        }
+
+        let update_impl = match &update_strategy {
+            Strategy::With(reducer) => quote! { #reducer(&mut self.#field_name, value) },
+            Strategy::Builtin(strategy) => match strategy.as_str() {
+                "append" => quote! { self.#field_name.extend(value) },
+                // Maps overwrite existing keys, sets take the union
+                "merge" | "union" => quote! { self.#field_name.extend(value.into_iter()) },
+                "replace" => quote! { self.#field_name = value },
+                "messages" => quote! {
+                    ::agentgraph_core::completion::add_messages(&mut self.#field_name, value)
+                },
+                "add" => quote! { self.#field_name += value },
+                "max" => quote! {
+                    if value > self.#field_name {
+                        self.#field_name = value
+                    }
+                },
+                "min" => quote! {
+                    if value < self.#field_name {
+                        self.#field_name = value
+                    }
+                },
+                strategy => panic!("Unknown update strategy: {}", strategy),
+            },
         };
 
         update_match_arms.push(quote! {
             #update_name::#variant_name(value) => { #update_impl }
         });
+
+        // Opt-in variants for shrinking the field
+        for extra in extras {
+            let extra_name = format_ident!("{}{}", extra.to_case(Case::Pascal), variant_name);
+            let (variant, arm) = match (extra.as_str(), collection_kind(&field_type)) {
+                ("clear", _) => (
+                    quote! { #extra_name },
+                    quote! {
+                        #update_name::#extra_name => {
+                            self.#field_name = ::std::default::Default::default()
+                        }
+                    },
+                ),
+                ("remove", Some((Collection::Sequence | Collection::Set, args))) => {
+                    // An update log would fail on them mid-run, and a schema
+                    // would leave out input that the state accepts
+                    if options.serde {
+                        panic!(
+                            "`remove` on {} takes a predicate, which `#[state(serde)]` cannot \
+                             serialize; use `clear` instead",
+                            field_name
+                        );
+                    }
+                    if options.schema {
+                        panic!(
+                            "`remove` on {} takes a predicate, which `#[state(schema)]` cannot \
+                             describe; use `clear` instead",
+                            field_name
+                        );
+                    }
+                    let item = &args[0];
+                    (
+                        quote! { #extra_name(::agentgraph_core::types::Predicate<#item>) },
+                        quote! {
+                            #update_name::#extra_name(predicate) => {
+                                self.#field_name.retain(|item| !predicate.matches(item))
+                            }
+                        },
+                    )
+                }
+                ("remove", Some((Collection::Map, args))) => {
+                    let key = &args[0];
+                    (
+                        quote! { #extra_name(Vec<#key>) },
+                        quote! {
+                            #update_name::#extra_name(keys) => {
+                                for key in keys {
+                                    self.#field_name.remove(&key);
+                                }
+                            }
+                        },
+                    )
+                }
+                ("insert", Some((Collection::Map, args))) => {
+                    let (key, value) = (&args[0], &args[1]);
+                    (
+                        quote! { #extra_name(#key, #value) },
+                        quote! {
+                            #update_name::#extra_name(key, value) => {
+                                self.#field_name.insert(key, value);
+                            }
+                        },
+                    )
+                }
+                ("remove", None) => panic!(
+                    "`remove` needs a Vec, VecDeque, set or map field: {}",
+                    field_name
+                ),
+                ("insert", _) => panic!("`insert` needs a map field: {}", field_name),
+                (extra, _) => panic!("Unknown update variant: {}", extra),
+            };
+            update_variants.push(variant);
+            update_match_arms.push(arm);
+        }
     }
 
+    let diff_impl = options.diff.then(|| {
+        quote! {
+            impl ::agentgraph_core::types::StateDiff for #name {
+                fn diff(&self, other: &Self) -> Vec<Self::Update> {
+                    let mut updates = vec![];
+                    #(#diff_blocks)*
+                    updates
+                }
+            }
+        }
+    });
+
+    let schema_attrs = options.schema.then(|| {
+        quote! {
+            #[derive(::agentgraph_core::schemars::JsonSchema)]
+            #[schemars(crate = "::agentgraph_core::schemars")]
+        }
+    });
+    let schema_impl = options.schema.then(|| {
+        quote! {
+            impl ::agentgraph_core::types::StateSchema for #name {
+                fn state_schema() -> ::agentgraph_core::serde_json::Value {
+                    <Self as ::agentgraph_core::tool::JsonSchema>::schema()
+                }
+
+                fn update_schema() -> ::agentgraph_core::serde_json::Value {
+                    <#update_name as ::agentgraph_core::tool::JsonSchema>::schema()
+                }
+            }
+        }
+    });
+
+    let serde_attrs = options.serde.then(|| {
+        quote! {
+            #[derive(::agentgraph_core::serde::Serialize, ::agentgraph_core::serde::Deserialize)]
+            #[serde(crate = "::agentgraph_core::serde")]
+        }
+    });
+
     let expanded = quote! {
+        #schema_attrs
+        #serde_attrs
         #[derive(Debug)]
         pub enum #update_name {
             #(#update_variants),*
@@ -65,7 +271,322 @@ pub fn derive_state_impl(input: TokenStream) -> TokenStream {
                 }
             }
         }
+
+        #diff_impl
+
+        #schema_impl
+
+        #messages_impl
     };
 
     TokenStream::from(expanded)
 }
+
+/// How a field is updated: a built-in strategy or a custom reducer
+enum Strategy {
+    Builtin(String),
+    With(syn::Path),
+}
+
+/// Extra update variants that can be requested next to the strategy
+const EXTRAS: [&str; 3] = ["clear", "remove", "insert"];
+
+/// Parsed `#[update(...)]` attribute of a field
+struct UpdateAttr {
+    strategy: Strategy,
+    extras: Vec<String>,
+}
+
+impl Default for UpdateAttr {
+    fn default() -> Self {
+        Self {
+            strategy: Strategy::Builtin("replace".to_string()),
+            extras: vec![],
+        }
+    }
+}
+
+/// Parse `#[update(strategy, extras...)]` or
+/// `#[update(with = path::to::reducer, extras...)]`. The strategy defaults to
+/// `replace` when only extras are given.
+fn parse_update_attr(attr: &syn::Attribute) -> UpdateAttr {
+    let mut parsed = UpdateAttr::default();
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("with") {
+            parsed.strategy = Strategy::With(meta.value()?.parse()?);
+        } else if let Some(ident) = meta.path.get_ident() {
+            let ident = ident.to_string();
+            if EXTRAS.contains(&ident.as_str()) {
+                parsed.extras.push(ident);
+            } else {
+                parsed.strategy = Strategy::Builtin(ident);
+            }
+        } else {
+            return Err(meta.error("Expected an update strategy"));
+        }
+        Ok(())
+    })
+    .unwrap_or_else(|e| panic!("Invalid update attribute: {}", e));
+    parsed
+}
+
+/// Kinds of collection fields that support `remove` and `insert`
+enum Collection {
+    /// `Vec` and `VecDeque`, removed from by predicate
+    Sequence,
+    /// Sets, removed from by predicate
+    Set,
+    /// Maps, removed from by key
+    Map,
+}
+
+/// Collection kind and generic arguments of a field type, if it is a
+/// known collection
+fn collection_kind(ty: &syn::Type) -> Option<(Collection, Vec<&syn::Type>)> {
+    let syn::Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let args: Vec<&syn::Type> = args
+        .args
+        .iter()
+        .filter_map(|arg| match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        })
+        .collect();
+    let kind = match segment.ident.to_string().as_str() {
+        "Vec" | "VecDeque" if !args.is_empty() => Collection::Sequence,
+        "HashSet" | "BTreeSet" if !args.is_empty() => Collection::Set,
+        "HashMap" | "BTreeMap" if args.len() >= 2 => Collection::Map,
+        _ => return None,
+    };
+    Some((kind, args))
+}
+
+/// Options given on the struct with `#[state(...)]`
+#[derive(Default)]
+struct StateOptions {
+    /// Implement `StateDiff`
+    diff: bool,
+    /// Derive a JSON Schema for the update enum and implement `StateSchema`
+    schema: bool,
+    /// Derive `Serialize` and `Deserialize` for the update enum
+    serde: bool,
+}
+
+fn parse_state_options(attrs: &[syn::Attribute]) -> StateOptions {
+    let mut options = StateOptions::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("state")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("diff") {
+                options.diff = true;
+                Ok(())
+            } else if meta.path.is_ident("schema") {
+                options.schema = true;
+                Ok(())
+            } else if meta.path.is_ident("serde") {
+                options.serde = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unknown state option"))
+            }
+        })
+        .unwrap_or_else(|e| panic!("Invalid state attribute: {}", e));
+    }
+    options
+}
+
+/// Whether a field is marked `#[state(messages)]` as the chat history
+fn is_messages_field(attrs: &[syn::Attribute]) -> bool {
+    let mut found = false;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("state")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("messages") {
+                found = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unknown field state option"))
+            }
+        })
+        .unwrap_or_else(|e| panic!("Invalid state attribute: {}", e));
+    }
+    found
+}
+
+/// Statements pushing the updates that turn `self.field` into `other.field`.
+/// Changes the strategy cannot express fall back to the `clear` variant
+/// followed by the full value for collections that have one, and to the
+/// generated `Set*` variant otherwise, so the diff is always exact.
+fn diff_field(
+    update_name: &syn::Ident,
+    variant_name: &syn::Ident,
+    field_name: &syn::Ident,
+    field_type: &syn::Type,
+    strategy: &Strategy,
+    extras: &[String],
+) -> proc_macro2::TokenStream {
+    let clear_name = format_ident!("Clear{}", variant_name);
+    let remove_name = format_ident!("Remove{}", variant_name);
+    let has = |extra: &str| extras.iter().any(|e| e == extra);
+    let this = quote! { self.#field_name };
+    let that = quote! { other.#field_name };
+
+    let strategy = match strategy {
+        Strategy::Builtin(strategy) => strategy.as_str(),
+        Strategy::With(_) => "with",
+    };
+
+    // Clearing then adding the full value only works for collections
+    let collects = matches!(strategy, "append" | "merge" | "union" | "messages");
+    let set = set_variant(variant_name);
+    let fallback = if has("clear") && collects {
+        quote! {
+            updates.push(#update_name::#clear_name);
+            updates.push(#update_name::#variant_name(#that.clone()));
+        }
+    } else {
+        quote! {
+            updates.push(#update_name::#set(#that.clone()));
+        }
+    };
+    let kind = collection_kind(field_type).map(|(kind, _)| kind);
+    let body = match (strategy, kind) {
+        ("replace", _) => quote! {
+            updates.push(#update_name::#variant_name(#that.clone()));
+        },
+        // Integers could overflow, other types could lose precision
+        ("add", _) if is_integer(field_type) => quote! {
+            match #that.checked_sub(#this) {
+                Some(delta) => updates.push(#update_name::#variant_name(delta)),
+                None => { #fallback }
+            }
+        },
+        ("add", _) => quote! {
+            let delta = #that.clone() - #this.clone();
+            let mut added = #this.clone();
+            added += delta.clone();
+            if added == #that {
+                updates.push(#update_name::#variant_name(delta));
+            } else {
+                #fallback
+            }
+        },
+        ("max", _) => quote! {
+            if #that > #this {
+                updates.push(#update_name::#variant_name(#that.clone()));
+            } else {
+                #fallback
+            }
+        },
+        ("min", _) => quote! {
+            if #that < #this {
+                updates.push(#update_name::#variant_name(#that.clone()));
+            } else {
+                #fallback
+            }
+        },
+        ("messages", _) => quote! {
+            match ::agentgraph_core::completion::diff_messages(&#this, &#that) {
+                Some(messages) => updates.push(#update_name::#variant_name(messages)),
+                None => { #fallback }
+            }
+        },
+        ("append" | "merge", Some(Collection::Sequence)) => quote! {
+            let is_prefix = #that.len() >= #this.len()
+                && #this.iter().zip(#that.iter()).all(|(a, b)| a == b);
+            if is_prefix {
+                let added = #that.iter().skip(#this.len()).cloned().collect();
+                updates.push(#update_name::#variant_name(added));
+            } else {
+                #fallback
+            }
+        },
+        ("append" | "merge" | "union", Some(kind @ (Collection::Set | Collection::Map))) => {
+            // Removed items or keys, the update removing them, and what to add
+            let (removed, remove, added) = match kind {
+                Collection::Map => (
+                    quote! { #this.keys().filter(|key| !#that.contains_key(*key)) },
+                    quote! { #update_name::#remove_name(removed) },
+                    quote! {
+                        #that
+                            .iter()
+                            .filter(|(key, value)| #this.get(*key) != Some(*value))
+                            .map(|(key, value)| (key.clone(), value.clone()))
+                    },
+                ),
+                _ => (
+                    quote! { #this.iter().filter(|item| !#that.contains(*item)) },
+                    quote! {
+                        #update_name::#remove_name(::agentgraph_core::types::Predicate::new(
+                            move |item| removed.contains(item),
+                        ))
+                    },
+                    quote! { #that.iter().filter(|item| !#this.contains(*item)).cloned() },
+                ),
+            };
+            let push_added = quote! {
+                let added: #field_type = #added.collect();
+                if !added.is_empty() {
+                    updates.push(#update_name::#variant_name(added));
+                }
+            };
+            if has("remove") {
+                quote! {
+                    let removed: Vec<_> = #removed.cloned().collect();
+                    if !removed.is_empty() {
+                        updates.push(#remove);
+                    }
+                    #push_added
+                }
+            } else {
+                quote! {
+                    if #removed.next().is_some() {
+                        #fallback
+                    } else {
+                        #push_added
+                    }
+                }
+            }
+        }
+        _ => fallback,
+    };
+
+    quote! {
+        if #this != #that {
+            #body
+        }
+    }
+}
+
+/// Name of the variant that sets a field outright, generated for every
+/// field of a `#[state(diff)]` struct whose strategy is not `replace`
+fn set_variant(variant_name: &syn::Ident) -> syn::Ident {
+    format_ident!("Set{}", variant_name)
+}
+
+/// Whether a type is a primitive integer, which has `checked_sub`
+fn is_integer(ty: &syn::Type) -> bool {
+    let syn::Type::Path(path) = ty else {
+        return false;
+    };
+    path.path.get_ident().is_some_and(|ident| {
+        matches!(
+            ident.to_string().as_str(),
+            "u8" | "u16"
+                | "u32"
+                | "u64"
+                | "u128"
+                | "usize"
+                | "i8"
+                | "i16"
+                | "i32"
+                | "i64"
+                | "i128"
+                | "isize"
+        )
+    })
+}
@@ -0,0 +1,16 @@
+use agentgraph_core::prelude::*;
+use agentgraph_core::types::JsonState;
+use agentgraph_macros::node;
+
+struct Agent;
+
+impl Agent {
+    // Without `self` the constructor calls the function unqualified, so it
+    // must be a free function
+    #[node]
+    async fn call(_ctx: &Context, _state: JsonState) -> NodeResult<JsonState> {
+        Ok(NodeOutput::Updates(vec![]))
+    }
+}
+
+fn main() {}
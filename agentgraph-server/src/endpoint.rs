@@ -0,0 +1,108 @@
+use crate::ServerError;
+use agentgraph_core::graph::GraphEvent;
+use agentgraph_core::{Built, Context, Graph, GraphState};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A mounted graph with its state type erased to JSON
+#[async_trait]
+pub(crate) trait ServedGraph: Send + Sync {
+    async fn invoke(&self, ctx: Context, input: Value) -> Result<Value, ServerError>;
+
+    fn stream(
+        &self,
+        ctx: Context,
+        input: Value,
+    ) -> Result<BoxStream<'static, GraphEvent<Value>>, ServerError>;
+
+    /// Apply a list of updates to a stored state, through the state's own
+    /// update semantics
+    fn resume(&self, state: Value, updates: Value) -> Result<Value, ServerError>;
+}
+
+pub(crate) struct GraphEndpoint<S> {
+    graph: Arc<Graph<S, Built>>,
+}
+
+impl<S> GraphEndpoint<S> {
+    pub(crate) fn new(graph: Graph<S, Built>) -> Self {
+        Self {
+            graph: Arc::new(graph),
+        }
+    }
+}
+
+fn to_state<S: DeserializeOwned>(input: Value) -> Result<S, ServerError> {
+    serde_json::from_value(input).map_err(|e| ServerError::InvalidState(e.to_string()))
+}
+
+fn to_updates<U: DeserializeOwned>(input: Value) -> Result<Vec<U>, ServerError> {
+    serde_json::from_value(input).map_err(|e| ServerError::InvalidUpdates(e.to_string()))
+}
+
+fn to_value<S: Serialize>(state: &S) -> Result<Value, ServerError> {
+    serde_json::to_value(state).map_err(|e| ServerError::Serialization(e.to_string()))
+}
+
+#[async_trait]
+impl<S> ServedGraph for GraphEndpoint<S>
+where
+    S: GraphState + Serialize + DeserializeOwned,
+    S::Update: DeserializeOwned,
+{
+    async fn invoke(&self, ctx: Context, input: Value) -> Result<Value, ServerError> {
+        let state = self.graph.run(&ctx, to_state(input)?).await?;
+        to_value(&state)
+    }
+
+    fn stream(
+        &self,
+        ctx: Context,
+        input: Value,
+    ) -> Result<BoxStream<'static, GraphEvent<Value>>, ServerError> {
+        let state: S = to_state(input)?;
+        Ok(graph_events(self.graph.clone(), ctx, state).boxed())
+    }
+
+    fn resume(&self, state: Value, updates: Value) -> Result<Value, ServerError> {
+        let mut state: S = to_state(state)?;
+        state.apply_many(to_updates(updates)?);
+        to_value(&state)
+    }
+}
+
+fn graph_events<S>(
+    graph: Arc<Graph<S, Built>>,
+    ctx: Context,
+    state: S,
+) -> impl Stream<Item = GraphEvent<Value>> + Send + 'static
+where
+    S: GraphState + Serialize,
+{
+    async_stream::stream! {
+        let events = graph.stream(&ctx, state);
+        futures::pin_mut!(events);
+        while let Some(event) = events.next().await {
+            let event = match event {
+                GraphEvent::Node { node, state } => {
+                    to_value(&state).map(|state| GraphEvent::Node { node, state })
+                }
+                GraphEvent::End { state } => to_value(&state).map(|state| GraphEvent::End { state }),
+                GraphEvent::Error { message } => Ok(GraphEvent::Error { message }),
+            };
+            // A state that cannot be serialized ends the stream with an error
+            match event {
+                Ok(event) => yield event,
+                Err(e) => {
+                    yield GraphEvent::Error { message: e.to_string() };
+                    break;
+                }
+            }
+        }
+    }
+}
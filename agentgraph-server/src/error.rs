@@ -0,0 +1,54 @@
+use agentgraph_core::GraphError;
+use axum::extract::rejection::JsonRejection;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use thiserror::Error;
+
+/// Error type for server operations
+#[derive(Error, Debug)]
+pub enum ServerError {
+    #[error("Graph not found: {0}")]
+    GraphNotFound(String),
+
+    #[error("Thread not found: {0}")]
+    ThreadNotFound(String),
+
+    #[error("Invalid state: {0}")]
+    InvalidState(String),
+
+    #[error("Invalid updates: {0}")]
+    InvalidUpdates(String),
+
+    #[error("Invalid request body: {0}")]
+    InvalidBody(#[from] JsonRejection),
+
+    #[error("Failed to serialize state: {0}")]
+    Serialization(String),
+
+    #[error(transparent)]
+    Graph(#[from] GraphError),
+}
+
+impl ServerError {
+    fn status(&self) -> StatusCode {
+        match self {
+            ServerError::GraphNotFound(_) | ServerError::ThreadNotFound(_) => StatusCode::NOT_FOUND,
+            ServerError::InvalidState(_) | ServerError::InvalidUpdates(_) => {
+                StatusCode::UNPROCESSABLE_ENTITY
+            }
+            ServerError::InvalidBody(rejection) => rejection.status(),
+            ServerError::Graph(_) | ServerError::Serialization(_) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+}
+
+impl IntoResponse for ServerError {
+    fn into_response(self) -> Response {
+        let body = Json(json!({ "error": self.to_string() }));
+        (self.status(), body).into_response()
+    }
+}
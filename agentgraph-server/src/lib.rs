@@ -0,0 +1,21 @@
+//! HTTP server for built AgentGraph graphs.
+//!
+//! Mounts one or more `Graph<S, Built>` instances behind JSON endpoints. Run
+//! endpoints take a `RunRequest` body, `{ "input": ..., "config": {...} }`:
+//!
+//! - `GET  /graphs` lists the mounted graphs
+//! - `POST /graphs/:graph/invoke` runs a graph on a JSON state
+//! - `POST /graphs/:graph/stream` runs a graph and streams node events over SSE
+//! - `GET  /graphs/:graph/threads/:thread_id` fetches the latest state of a thread
+//! - `POST /graphs/:graph/threads/:thread_id/invoke` continues a thread with a
+//!   list of updates
+//! - `POST /graphs/:graph/threads/:thread_id/stream` continues a thread over SSE
+
+mod endpoint;
+mod error;
+mod server;
+mod threads;
+
+pub use error::ServerError;
+pub use server::{GraphServer, RunRequest, ThreadState};
+pub use threads::ThreadStore;
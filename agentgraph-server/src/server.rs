@@ -0,0 +1,246 @@
+use crate::endpoint::{GraphEndpoint, ServedGraph};
+use crate::{ServerError, ThreadStore};
+use agentgraph_core::graph::GraphEvent;
+use agentgraph_core::{Built, Context, Graph, GraphState, RunConfig};
+use axum::async_trait;
+use axum::extract::{FromRequest, Path, Request, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio::net::{TcpListener, ToSocketAddrs};
+
+/// Latest state of a thread, as returned by the thread endpoints
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ThreadState {
+    pub thread_id: String,
+    pub state: Value,
+}
+
+/// Body of the run endpoints. `input` is the initial state, or for a thread
+/// that has run before, a list of updates to apply to its latest state.
+/// `config` is merged over the server's run configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RunRequest {
+    pub input: Value,
+    #[serde(default)]
+    pub config: RunConfig,
+}
+
+/// Serves built graphs over HTTP
+#[derive(Clone, Default)]
+pub struct GraphServer {
+    graphs: HashMap<String, Arc<dyn ServedGraph>>,
+    threads: ThreadStore,
+    context: Context,
+}
+
+impl GraphServer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mount a graph under its own name. Its updates must deserialize, see
+    /// `#[state(serde)]`, so threads can be continued.
+    pub fn mount<S>(self, graph: Graph<S, Built>) -> Self
+    where
+        S: GraphState + Serialize + DeserializeOwned,
+        S::Update: DeserializeOwned,
+    {
+        let name = graph.graph_name().to_string();
+        self.mount_as(name, graph)
+    }
+
+    /// Mount a graph under the given name
+    pub fn mount_as<S>(mut self, name: impl Into<String>, graph: Graph<S, Built>) -> Self
+    where
+        S: GraphState + Serialize + DeserializeOwned,
+        S::Update: DeserializeOwned,
+    {
+        self.graphs
+            .insert(name.into(), Arc::new(GraphEndpoint::new(graph)));
+        self
+    }
+
+    /// Use a shared thread store, e.g. to inspect threads from outside the server
+    pub fn with_threads(mut self, threads: ThreadStore) -> Self {
+        self.threads = threads;
+        self
+    }
+
+    /// Base context of every run, e.g. to share a store, extensions or a
+    /// default run configuration. Each request runs in a child context.
+    pub fn with_context(mut self, context: Context) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Build the axum router for the mounted graphs
+    pub fn router(self) -> Router {
+        Router::new()
+            .route("/graphs", get(list_graphs))
+            .route("/graphs/:graph/invoke", post(invoke))
+            .route("/graphs/:graph/stream", post(stream))
+            .route("/graphs/:graph/threads/:thread_id", get(get_thread))
+            .route(
+                "/graphs/:graph/threads/:thread_id/invoke",
+                post(invoke_thread),
+            )
+            .route(
+                "/graphs/:graph/threads/:thread_id/stream",
+                post(stream_thread),
+            )
+            .with_state(Arc::new(self))
+    }
+
+    /// Listen on `addr` and serve the mounted graphs until the process exits
+    pub async fn serve(self, addr: impl ToSocketAddrs) -> std::io::Result<()> {
+        let listener = TcpListener::bind(addr).await?;
+        axum::serve(listener, self.router()).await
+    }
+
+    fn graph(&self, name: &str) -> Result<&Arc<dyn ServedGraph>, ServerError> {
+        self.graphs
+            .get(name)
+            .ok_or_else(|| ServerError::GraphNotFound(name.to_string()))
+    }
+
+    /// Context of a single run, with the request's configuration merged over
+    /// the base configuration
+    fn context(&self, config: RunConfig) -> Context {
+        let mut ctx = self.context.next_node_context();
+        ctx.run_config.merge(config);
+        ctx
+    }
+
+    /// Initial state for continuing a thread: the input updates are applied
+    /// to the thread's latest state, or the input is used as-is for a new
+    /// thread
+    fn thread_input(
+        &self,
+        graph: &str,
+        thread_id: &str,
+        input: Value,
+    ) -> Result<Value, ServerError> {
+        match self.threads.get(graph, thread_id) {
+            Some(state) => self.graph(graph)?.resume(state, input),
+            None => Ok(input),
+        }
+    }
+}
+
+type ServerState = State<Arc<GraphServer>>;
+
+/// JSON request body whose rejections are reported as `ServerError`s, so
+/// clients always get a JSON error instead of axum's plain text
+struct JsonBody<T>(T);
+
+#[async_trait]
+impl<S, T> FromRequest<S> for JsonBody<T>
+where
+    S: Send + Sync,
+    T: DeserializeOwned,
+{
+    type Rejection = ServerError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state).await?;
+        Ok(Self(value))
+    }
+}
+
+async fn list_graphs(State(server): ServerState) -> Json<Vec<String>> {
+    let mut names: Vec<String> = server.graphs.keys().cloned().collect();
+    names.sort();
+    Json(names)
+}
+
+async fn invoke(
+    State(server): ServerState,
+    Path(graph): Path<String>,
+    JsonBody(request): JsonBody<RunRequest>,
+) -> Result<Json<Value>, ServerError> {
+    let ctx = server.context(request.config);
+    let state = server.graph(&graph)?.invoke(ctx, request.input).await?;
+    Ok(Json(state))
+}
+
+async fn stream(
+    State(server): ServerState,
+    Path(graph): Path<String>,
+    JsonBody(request): JsonBody<RunRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ServerError> {
+    let ctx = server.context(request.config);
+    let events = server.graph(&graph)?.stream(ctx, request.input)?;
+    Ok(sse(events))
+}
+
+async fn get_thread(
+    State(server): ServerState,
+    Path((graph, thread_id)): Path<(String, String)>,
+) -> Result<Json<ThreadState>, ServerError> {
+    server.graph(&graph)?;
+    let state = server
+        .threads
+        .get(&graph, &thread_id)
+        .ok_or_else(|| ServerError::ThreadNotFound(thread_id.clone()))?;
+    Ok(Json(ThreadState { thread_id, state }))
+}
+
+async fn invoke_thread(
+    State(server): ServerState,
+    Path((graph, thread_id)): Path<(String, String)>,
+    JsonBody(request): JsonBody<RunRequest>,
+) -> Result<Json<ThreadState>, ServerError> {
+    let input = server.thread_input(&graph, &thread_id, request.input)?;
+    let ctx = server
+        .context(request.config)
+        .with_metadata("thread_id", &thread_id);
+    let state = server.graph(&graph)?.invoke(ctx, input).await?;
+    server.threads.put(&graph, &thread_id, state.clone());
+    Ok(Json(ThreadState { thread_id, state }))
+}
+
+async fn stream_thread(
+    State(server): ServerState,
+    Path((graph, thread_id)): Path<(String, String)>,
+    JsonBody(request): JsonBody<RunRequest>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ServerError> {
+    let input = server.thread_input(&graph, &thread_id, request.input)?;
+    let ctx = server
+        .context(request.config)
+        .with_metadata("thread_id", &thread_id);
+    let events = server.graph(&graph)?.stream(ctx, input)?;
+
+    // Save the final state once the run completes
+    let threads = server.threads.clone();
+    let events = events.inspect(move |event| {
+        if let GraphEvent::End { state } = event {
+            threads.put(&graph, &thread_id, state.clone());
+        }
+    });
+    Ok(sse(events))
+}
+
+fn sse(
+    events: impl Stream<Item = GraphEvent<Value>> + Send + 'static,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let events = events.map(|event| {
+        let name = match &event {
+            GraphEvent::Node { .. } => "node",
+            GraphEvent::End { .. } => "end",
+            GraphEvent::Error { .. } => "error",
+        };
+        Ok(Event::default()
+            .event(name)
+            .json_data(&event)
+            .unwrap_or_else(|e| Event::default().event("error").data(e.to_string())))
+    });
+    Sse::new(events).keep_alive(KeepAlive::default())
+}
@@ -0,0 +1,33 @@
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// In-memory store of the latest state of each thread, keyed by graph name
+/// and thread id
+#[derive(Debug, Default, Clone)]
+pub struct ThreadStore {
+    threads: Arc<RwLock<HashMap<(String, String), Value>>>,
+}
+
+impl ThreadStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Latest state of a thread, if it has been run before
+    pub fn get(&self, graph: &str, thread_id: &str) -> Option<Value> {
+        self.threads
+            .read()
+            .unwrap()
+            .get(&(graph.to_string(), thread_id.to_string()))
+            .cloned()
+    }
+
+    /// Record the latest state of a thread
+    pub fn put(&self, graph: &str, thread_id: &str, state: Value) {
+        self.threads
+            .write()
+            .unwrap()
+            .insert((graph.to_string(), thread_id.to_string()), state);
+    }
+}
@@ -0,0 +1,334 @@
+use agentgraph_core::completion::Message;
+use agentgraph_core::prelude::*;
+use agentgraph_macros::State;
+use agentgraph_server::GraphServer;
+use axum::body::Body;
+use axum::http::{Request, StatusCode};
+use axum::Router;
+use http_body_util::BodyExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use tower::ServiceExt;
+
+#[derive(State, Debug, Clone, Serialize, Deserialize)]
+#[state(serde)]
+struct CounterState {
+    #[update(replace)]
+    count: i32,
+
+    #[update(append)]
+    history: Vec<String>,
+}
+
+fn counter_graph() -> Graph<CounterState, Built> {
+    let increment = FunctionNode::new("increment", |_ctx, state: CounterState| async move {
+        Ok(NodeOutput::Updates(vec![
+            CounterStateUpdate::Count(state.count + 1),
+            CounterStateUpdate::History(vec!["increment".to_string()]),
+        ]))
+    });
+    let double = FunctionNode::new("double", |_ctx, state: CounterState| async move {
+        Ok(NodeOutput::Updates(vec![
+            CounterStateUpdate::Count(state.count * 2),
+            CounterStateUpdate::History(vec!["double".to_string()]),
+        ]))
+    });
+
+    let mut graph = Graph::new("counter");
+    graph
+        .add_node(increment)
+        .add_node(double)
+        .add_edge(START, "increment")
+        .add_edge("increment", "double")
+        .add_edge("double", END);
+    graph.build()
+}
+
+fn router() -> Router {
+    GraphServer::new().mount(counter_graph()).router()
+}
+
+async fn send(
+    router: &Router,
+    method: &str,
+    uri: &str,
+    body: Option<Value>,
+) -> (StatusCode, String) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json")
+        .body(match body {
+            Some(body) => Body::from(body.to_string()),
+            None => Body::empty(),
+        })
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, String::from_utf8(bytes.to_vec()).unwrap())
+}
+
+#[tokio::test]
+async fn test_list_graphs() {
+    let (status, body) = send(&router(), "GET", "/graphs", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_str::<Value>(&body).unwrap(),
+        json!(["counter"])
+    );
+}
+
+#[tokio::test]
+async fn test_invoke() {
+    let input = json!({ "input": { "count": 1, "history": [] } });
+    let (status, body) = send(&router(), "POST", "/graphs/counter/invoke", Some(input)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        serde_json::from_str::<Value>(&body).unwrap(),
+        json!({ "count": 4, "history": ["increment", "double"] })
+    );
+}
+
+#[tokio::test]
+async fn test_invoke_errors() {
+    let router = router();
+
+    let input = json!({ "input": {} });
+    let (status, _) = send(&router, "POST", "/graphs/missing/invoke", Some(input)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let input = json!({ "input": { "count": "one" } });
+    let (status, body) = send(&router, "POST", "/graphs/counter/invoke", Some(input)).await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.contains("Invalid state"));
+
+    // Malformed bodies get the same JSON error shape
+    let request = Request::builder()
+        .method("POST")
+        .uri("/graphs/counter/invoke")
+        .header("content-type", "application/json")
+        .body(Body::from("{ not json"))
+        .unwrap();
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&bytes).unwrap();
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .starts_with("Invalid request body"));
+}
+
+// JSON object keys must be strings, so a filled `cells` cannot be serialized
+#[derive(State, Debug, Clone, Serialize, Deserialize)]
+#[state(serde)]
+struct GridState {
+    #[update(replace)]
+    #[serde(skip_deserializing)]
+    cells: HashMap<(u8, u8), u8>,
+}
+
+#[tokio::test]
+async fn test_unserializable_state() {
+    let fill = FunctionNode::new("fill", |_ctx, _state: GridState| async move {
+        Ok(NodeOutput::Updates(vec![GridStateUpdate::Cells(
+            HashMap::from([((0, 0), 1)]),
+        )]))
+    });
+    let mut graph = Graph::new("grid");
+    graph
+        .add_node(fill)
+        .add_edge(START, "fill")
+        .add_edge("fill", END);
+    let router = GraphServer::new().mount(graph.build()).router();
+
+    let uri = "/graphs/grid/threads/t1/invoke";
+    let (status, body) = send(&router, "POST", uri, Some(json!({ "input": {} }))).await;
+    assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(body.contains("Failed to serialize state"));
+
+    // Nothing is stored for the thread
+    let (status, _) = send(&router, "GET", "/graphs/grid/threads/t1", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let input = json!({ "input": {} });
+    let (status, body) = send(&router, "POST", "/graphs/grid/stream", Some(input)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body.contains("event: error"));
+    assert!(!body.contains("event: end"));
+}
+
+#[tokio::test]
+async fn test_stream() {
+    let input = json!({ "input": { "count": 1, "history": [] } });
+    let (status, body) = send(&router(), "POST", "/graphs/counter/stream", Some(input)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let events: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: "))
+        .collect();
+    assert_eq!(events, vec!["node", "node", "end"]);
+
+    let data: Vec<Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data: "))
+        .map(|data| serde_json::from_str(data).unwrap())
+        .collect();
+    assert_eq!(data[0]["node"], "increment");
+    assert_eq!(data[0]["state"]["count"], 2);
+    assert_eq!(data[2]["state"]["count"], 4);
+}
+
+#[tokio::test]
+async fn test_threads() {
+    let router = router();
+
+    let (status, _) = send(&router, "GET", "/graphs/counter/threads/t1", None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // A new thread starts from the given state
+    let input = json!({ "input": { "count": 1, "history": [] } });
+    let (status, body) = send(
+        &router,
+        "POST",
+        "/graphs/counter/threads/t1/invoke",
+        Some(input),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let thread: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(thread["thread_id"], "t1");
+    assert_eq!(thread["state"]["count"], 4);
+
+    // Continuing applies the given updates to the latest state
+    let input = json!({ "input": [{ "History": ["resume"] }] });
+    let (_, body) = send(
+        &router,
+        "POST",
+        "/graphs/counter/threads/t1/stream",
+        Some(input),
+    )
+    .await;
+    assert!(body.contains("event: end"));
+
+    let (status, body) = send(&router, "GET", "/graphs/counter/threads/t1", None).await;
+    assert_eq!(status, StatusCode::OK);
+    let thread: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(thread["state"]["count"], 10);
+    assert_eq!(
+        thread["state"]["history"],
+        json!(["increment", "double", "resume", "increment", "double"])
+    );
+
+    // Updates that do not fit the state are rejected
+    let input = json!({ "input": { "count": 2 } });
+    let (status, body) = send(
+        &router,
+        "POST",
+        "/graphs/counter/threads/t1/invoke",
+        Some(input),
+    )
+    .await;
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.contains("Invalid updates"));
+}
+
+#[derive(State, Debug, Clone, Serialize, Deserialize)]
+#[state(serde)]
+struct ChatState {
+    #[update(messages)]
+    messages: Vec<Message>,
+}
+
+fn message(id: &str, role: &str, content: &str) -> Value {
+    json!({ "id": id, "message": { "role": role, "content": content } })
+}
+
+#[tokio::test]
+async fn test_thread_keeps_history() {
+    let reply = FunctionNode::new("reply", |_ctx, state: ChatState| async move {
+        let turn = state.messages.len();
+        let reply = serde_json::from_value(message(&turn.to_string(), "assistant", "ok")).unwrap();
+        Ok(NodeOutput::Updates(vec![ChatStateUpdate::Messages(vec![
+            reply,
+        ])]))
+    });
+    let mut graph = Graph::new("chat");
+    graph
+        .add_node(reply)
+        .add_edge(START, "reply")
+        .add_edge("reply", END);
+    let router = GraphServer::new().mount(graph.build()).router();
+
+    let uri = "/graphs/chat/threads/t1/invoke";
+    let input = json!({ "input": { "messages": [message("q1", "user", "hi")] } });
+    let (status, _) = send(&router, "POST", uri, Some(input)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // A new message is added to the conversation instead of replacing it
+    let input = json!({ "input": [{ "Messages": [message("q2", "user", "again")] }] });
+    let (status, body) = send(&router, "POST", uri, Some(input)).await;
+    assert_eq!(status, StatusCode::OK);
+    let thread: Value = serde_json::from_str(&body).unwrap();
+    let ids: Vec<&str> = thread["state"]["messages"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|message| message["id"].as_str().unwrap())
+        .collect();
+    assert_eq!(ids, vec!["q1", "1", "q2", "3"]);
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct GreetConfig {
+    greeting: String,
+    name: String,
+}
+
+#[tokio::test]
+async fn test_context_and_config() {
+    let greet = FunctionNode::new("greet", |ctx: &Context, _state: CounterState| {
+        let config = ctx.config::<GreetConfig>();
+        let suffix = ctx.extension::<String>().cloned().unwrap_or_default();
+        async move {
+            let config = config?;
+            Ok(NodeOutput::Updates(vec![CounterStateUpdate::History(
+                vec![format!("{} {}{}", config.greeting, config.name, suffix)],
+            )]))
+        }
+    });
+    let mut graph = Graph::new("greet");
+    graph
+        .add_node(greet)
+        .add_edge(START, "greet")
+        .add_edge("greet", END);
+    let context = Context::default()
+        .with_extension("!".to_string())
+        .with_run_config(
+            RunConfig::new()
+                .with("greeting", "hello")
+                .with("name", "world"),
+        );
+    let router = GraphServer::new()
+        .with_context(context)
+        .mount(graph.build())
+        .router();
+
+    // The server's context is used for every run
+    let input = json!({ "input": { "count": 0, "history": [] } });
+    let (_, body) = send(&router, "POST", "/graphs/greet/invoke", Some(input)).await;
+    let state: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(state["history"], json!(["hello world!"]));
+
+    // Request config is merged over the server's config
+    let input = json!({
+        "input": { "count": 0, "history": [] },
+        "config": { "name": "there" }
+    });
+    let (_, body) = send(&router, "POST", "/graphs/greet/invoke", Some(input)).await;
+    let state: Value = serde_json::from_str(&body).unwrap();
+    assert_eq!(state["history"], json!(["hello there!"]));
+}
@@ -0,0 +1,42 @@
+use agentgraph_core::prelude::*;
+use agentgraph_core::schemars::JsonSchema;
+use agentgraph_core::types::StateSchema;
+use agentgraph_macros::State;
+
+#[derive(State, Debug, Clone, Default, JsonSchema)]
+#[schemars(crate = "agentgraph_core::schemars")]
+#[state(schema, serde, diff)]
+pub struct ReviewState {
+    #[update(replace)]
+    title: String,
+
+    #[update(append, clear)]
+    comments: Vec<String>,
+
+    #[update(add)]
+    approvals: u32,
+}
+
+fn main() {
+    let mut state = ReviewState::default();
+    state.apply_many([
+        ReviewStateUpdate::Title("Add schema example".to_string()),
+        ReviewStateUpdate::Comments(vec!["Looks good".to_string()]),
+        ReviewStateUpdate::Approvals(1),
+    ]);
+    println!("State: {:?}", state);
+    println!("State schema: {}", ReviewState::state_schema());
+    println!("Update schema: {}", ReviewState::update_schema());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schema_without_serde_json() {
+        let schema = ReviewState::update_schema();
+        assert!(schema.to_string().contains("Comments"));
+        assert!(ReviewState::state_schema()["properties"]["approvals"].is_object());
+    }
+}